@@ -0,0 +1,380 @@
+use crate::{
+    chain_adapter::{ChainAdapter, EvmAdapter},
+    checkpoint::{AnchorRecord, CheckpointMsg},
+    digichain::DigiChain,
+    types::Address,
+};
+use async_trait::async_trait;
+use ethers::{
+    contract::abigen,
+    providers::Middleware,
+    types::{Bytes, H256, U256 as EthU256, U64},
+};
+use std::{error::Error as StdError, str::FromStr, sync::Arc, time::Duration};
+
+abigen!(
+    AnchorContract,
+    r#"[
+        function anchorCheckpoint(string chainId, uint256 blockNumber, bytes32 blockHash, bytes32 stateRoot, bytes[] sigs) external
+    ]"#
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnchorReceipt {
+    pub success: bool,
+    pub block_number: u64,
+}
+
+// access to anchor contract, stand-in implementations can be used in place of provider
+#[async_trait]
+pub trait AnchorClient: Send + Sync {
+    // posts checkpoint to anchor contract, returns hash of tx on anchor chain
+    async fn submit_checkpoint(
+        &self,
+        contract: Address,
+        checkpoint: &CheckpointMsg,
+    ) -> Result<H256, Box<dyn StdError + Send + Sync>>;
+
+    // none while tx is not mined yet
+    async fn get_anchor_receipt(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<AnchorReceipt>, Box<dyn StdError + Send + Sync>>;
+}
+
+// anchor contract on evm chain, client should sign with validator wallet
+#[derive(Clone, Debug)]
+pub struct EvmAnchorClient<M: Middleware> {
+    pub client: Arc<M>,
+}
+
+impl<M: Middleware> EvmAnchorClient<M> {
+    pub fn new(client: Arc<M>) -> EvmAnchorClient<M> {
+        EvmAnchorClient { client }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + 'static> AnchorClient for EvmAnchorClient<M> {
+    async fn submit_checkpoint(
+        &self,
+        contract: Address,
+        checkpoint: &CheckpointMsg,
+    ) -> Result<H256, Box<dyn StdError + Send + Sync>> {
+        let sigs = checkpoint
+            .sigs
+            .iter()
+            .map(|sig| Bytes::from(EvmAdapter.encode_signature(sig)))
+            .collect::<Vec<Bytes>>();
+        let contract = AnchorContract::new(contract, self.client.clone());
+        let call = contract.anchor_checkpoint(
+            checkpoint.chain_id.clone(),
+            EthU256::from(checkpoint.block_number),
+            checkpoint.block_hash.to_fixed_bytes(),
+            checkpoint.state_root.to_fixed_bytes(),
+            sigs,
+        );
+        let res = call.send().await.map(|pending| pending.tx_hash());
+        if res.is_err() {
+            return Err(format!("anchorCheckpoint: {:?}", res.err()).into());
+        }
+        Ok(res.unwrap())
+    }
+
+    async fn get_anchor_receipt(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<AnchorReceipt>, Box<dyn StdError + Send + Sync>> {
+        let res = self.client.get_transaction_receipt(tx_hash).await;
+        if res.is_err() {
+            return Err(format!("get_transaction_receipt: {:?}", res.err()).into());
+        }
+        let res = res.unwrap();
+        if res.is_none() {
+            return Ok(None);
+        }
+        let receipt = res.unwrap();
+        Ok(Some(AnchorReceipt {
+            success: receipt.status == Some(U64::from(1u64)),
+            block_number: receipt.block_number.map_or(0u64, |number| number.as_u64()),
+        }))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingAnchor {
+    pub checkpoint: CheckpointMsg,
+    pub tx_hash: H256,
+}
+
+// posts latest quorum signed checkpoint to anchor contract registered in contract configs
+pub struct Anchorer<C: AnchorClient> {
+    pub anchor_chain_id: String,
+    pub poll_interval: u64,
+    pub client: C,
+    pub pending: Option<PendingAnchor>, // submitted, waiting to be mined
+}
+
+impl<C: AnchorClient> Anchorer<C> {
+    pub fn new(anchor_chain_id: String, poll_interval: u64, client: C) -> Anchorer<C> {
+        Anchorer {
+            anchor_chain_id,
+            poll_interval,
+            client,
+            pending: None,
+        }
+    }
+
+    pub async fn run(&mut self, digichain: DigiChain) {
+        loop {
+            if !digichain.pause.read().unwrap().clone() {
+                self.confirm_pending(&digichain).await;
+                self.anchor_latest_checkpoint(&digichain).await;
+            }
+            tokio::time::sleep(Duration::from_secs(self.poll_interval)).await;
+        }
+    }
+
+    // submits latest checkpoint if it is newer than last anchored one, returns submitted tx hash
+    pub async fn anchor_latest_checkpoint(&mut self, digichain: &DigiChain) -> Option<H256> {
+        if self.pending.is_some() {
+            return None;
+        }
+        let (checkpoint, last_anchor) = {
+            let crosschain = digichain.crosschain.read().unwrap();
+            (
+                crosschain.get_latest_checkpoint(),
+                crosschain.get_last_anchor(),
+            )
+        };
+        if checkpoint.is_none() {
+            return None;
+        }
+        let checkpoint = checkpoint.unwrap();
+        if last_anchor.map_or(false, |anchor| {
+            anchor.block_number >= checkpoint.block_number
+        }) {
+            return None;
+        }
+        let contract = self.get_contract_address(digichain);
+        if contract.is_none() {
+            return None;
+        }
+        let res = self
+            .client
+            .submit_checkpoint(contract.unwrap(), &checkpoint)
+            .await;
+        if res.is_err() {
+            println!(
+                "Anchor Failed || BlockNumber: {}, Error: {:?}",
+                checkpoint.block_number,
+                res.err()
+            );
+            return None;
+        }
+        let tx_hash = res.unwrap();
+        println!(
+            "Anchor Submitted || BlockNumber: {}, TxHash: {:?}",
+            checkpoint.block_number, tx_hash
+        );
+        self.pending = Some(PendingAnchor {
+            checkpoint,
+            tx_hash,
+        });
+        Some(tx_hash)
+    }
+
+    // records pending anchor once mined, reverted one is submitted again in next round
+    pub async fn confirm_pending(&mut self, digichain: &DigiChain) -> Option<AnchorRecord> {
+        if self.pending.is_none() {
+            return None;
+        }
+        let pending = self.pending.clone().unwrap();
+        let res = self.client.get_anchor_receipt(pending.tx_hash).await;
+        if res.is_err() {
+            println!(
+                "Anchor Receipt Failed || TxHash: {:?}, Error: {:?}",
+                pending.tx_hash,
+                res.err()
+            );
+            return None;
+        }
+        let res = res.unwrap();
+        if res.is_none() {
+            return None;
+        }
+        let receipt = res.unwrap();
+        self.pending = None;
+        if !receipt.success {
+            println!(
+                "Anchor Reverted || BlockNumber: {}, TxHash: {:?}",
+                pending.checkpoint.block_number, pending.tx_hash
+            );
+            return None;
+        }
+        let anchor = AnchorRecord {
+            anchor_chain_id: self.anchor_chain_id.clone(),
+            block_number: pending.checkpoint.block_number,
+            block_hash: pending.checkpoint.block_hash,
+            state_root: pending.checkpoint.state_root,
+            tx_hash: pending.tx_hash,
+            anchor_block_number: receipt.block_number,
+        };
+        digichain
+            .crosschain
+            .write()
+            .unwrap()
+            .record_anchor(anchor.clone());
+        println!(
+            "Anchor Recorded || BlockNumber: {}, AnchorBlockNumber: {}",
+            anchor.block_number, anchor.anchor_block_number
+        );
+        Some(anchor)
+    }
+
+    fn get_contract_address(&self, digichain: &DigiChain) -> Option<Address> {
+        let res = digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .get_contracts_config(vec![self.anchor_chain_id.clone()])
+            .remove(&self.anchor_chain_id);
+        if res.is_none() {
+            return None;
+        }
+        let contract_config = res.unwrap();
+        if contract_config.disabled {
+            return None;
+        }
+        Address::from_str(&contract_config.contract_address).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ChainTypeEvm;
+    use std::sync::Mutex;
+
+    // anchor contract kept in memory, every submitted checkpoint gets mined at once
+    struct StandInAnchorClient {
+        success: bool,
+        submitted: Mutex<Vec<u64>>,
+    }
+
+    impl StandInAnchorClient {
+        fn new(success: bool) -> StandInAnchorClient {
+            StandInAnchorClient {
+                success,
+                submitted: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AnchorClient for StandInAnchorClient {
+        async fn submit_checkpoint(
+            &self,
+            _contract: Address,
+            checkpoint: &CheckpointMsg,
+        ) -> Result<H256, Box<dyn StdError + Send + Sync>> {
+            let mut submitted = self.submitted.lock().unwrap();
+            submitted.push(checkpoint.block_number);
+            Ok(H256::from_low_u64_be(submitted.len() as u64))
+        }
+
+        async fn get_anchor_receipt(
+            &self,
+            _tx_hash: H256,
+        ) -> Result<Option<AnchorReceipt>, Box<dyn StdError + Send + Sync>> {
+            Ok(Some(AnchorReceipt {
+                success: self.success,
+                block_number: 20u64,
+            }))
+        }
+    }
+
+    // chain with anchor contract configured and checkpoint of block 5 signed by quorum
+    fn get_digichain() -> DigiChain {
+        let digichain = DigiChain::default();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        crosschain
+            .add_contract_config(
+                "ethereum".to_string(),
+                format!("{:?}", Address::from_low_u64_be(2u64)),
+                0u64,
+                ChainTypeEvm,
+            )
+            .unwrap();
+        crosschain
+            .add_checkpoint(CheckpointMsg::new(
+                "digichain".to_string(),
+                5u64,
+                H256::from_low_u64_be(6u64),
+                H256::from_low_u64_be(7u64),
+            ))
+            .unwrap();
+        drop(crosschain);
+        digichain
+    }
+
+    #[actix_web::test]
+    async fn anchors_checkpoint_once_mined() {
+        let digichain = get_digichain();
+        let mut anchorer =
+            Anchorer::new("ethereum".to_string(), 1u64, StandInAnchorClient::new(true));
+
+        assert!(anchorer
+            .anchor_latest_checkpoint(&digichain)
+            .await
+            .is_some());
+        // waits for pending one to be mined
+        assert!(anchorer
+            .anchor_latest_checkpoint(&digichain)
+            .await
+            .is_none());
+
+        let anchor = anchorer.confirm_pending(&digichain).await.unwrap();
+        assert_eq!(anchor.block_number, 5u64);
+        assert_eq!(anchor.anchor_block_number, 20u64);
+        assert_eq!(
+            digichain.crosschain.read().unwrap().get_last_anchor(),
+            Some(anchor)
+        );
+        // latest checkpoint is anchored already
+        assert!(anchorer
+            .anchor_latest_checkpoint(&digichain)
+            .await
+            .is_none());
+        assert_eq!(anchorer.client.submitted.lock().unwrap().len(), 1usize);
+    }
+
+    #[actix_web::test]
+    async fn reverted_anchor_gets_submitted_again() {
+        let digichain = get_digichain();
+        let mut anchorer = Anchorer::new(
+            "ethereum".to_string(),
+            1u64,
+            StandInAnchorClient::new(false),
+        );
+
+        assert!(anchorer
+            .anchor_latest_checkpoint(&digichain)
+            .await
+            .is_some());
+        assert!(anchorer.confirm_pending(&digichain).await.is_none());
+        assert!(anchorer.pending.is_none());
+        assert!(digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .get_last_anchor()
+            .is_none());
+
+        assert!(anchorer
+            .anchor_latest_checkpoint(&digichain)
+            .await
+            .is_some());
+        assert_eq!(*anchorer.client.submitted.lock().unwrap(), vec![5u64, 5u64]);
+    }
+}
@@ -14,6 +14,8 @@ pub struct DigiBlock {
     pub sign: String,
     pub proposed_by: Address,
     pub hash: String,
+    #[serde(default)]
+    pub state_root: String, // state after executing block, see DigiChain::get_state_root
 }
 
 impl DigiBlock {
@@ -35,6 +37,7 @@ impl DigiBlock {
         previous_hash: String,
         transactions: Vec<Transaction>,
         proposals: Vec<Proposal>,
+        state_root: String,
     ) -> DigiBlock {
         let mut block = DigiBlock {
             index: block_number,
@@ -46,6 +49,7 @@ impl DigiBlock {
             hash: "".to_string(),
             sign: "".to_string(),
             proposed_by: validator.acccount.address,
+            state_root,
         };
         block.merkle_root = block.clone().calculate_merkle_root();
         block.hash = block.clone().get_block_hash();
@@ -54,8 +58,13 @@ impl DigiBlock {
 
     fn get_block_hash(self) -> String {
         let block_string = format!(
-            "{}{}{}{:?}{}",
-            self.index, self.timestamp, self.merkle_root, self.transactions, self.previous_hash
+            "{}{}{}{:?}{}{}",
+            self.index,
+            self.timestamp,
+            self.merkle_root,
+            self.transactions,
+            self.previous_hash,
+            self.state_root
         );
         let mut hasher = Sha256::new();
         hasher.update(&block_string);
@@ -97,6 +106,7 @@ impl Default for DigiBlock {
             sign: Default::default(),
             proposed_by: Default::default(),
             proposals: Default::default(),
+            state_root: Default::default(),
         }
     }
 }
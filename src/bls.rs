@@ -0,0 +1,236 @@
+use crate::types::HexString;
+use blst::min_pk::{AggregateSignature, PublicKey, SecretKey, Signature};
+use blst::BLST_ERROR;
+use rand::Rng;
+use std::error::Error as StdError;
+
+// proof of possession ciphersuite, gateway has to use same dst while verifying
+pub const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// proof of possession is signature over own public key with separate dst, keys without valid one
+// are not aggregated (rogue key attack)
+pub const BLS_POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// bls key is kept apart from validator wallet, leaking one of them doesn't leak the other
+pub fn generate_secret_key() -> Result<SecretKey, Box<dyn StdError>> {
+    let ikm: [u8; 32] = rand::thread_rng().gen();
+    let res = SecretKey::key_gen(&ikm, b"digichain-bls");
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    Ok(res.unwrap())
+}
+
+// 32 byte big endian scalar, as serialized by generate_secret_key().serialize()
+pub fn load_secret_key(secret_key: &HexString) -> Result<SecretKey, Box<dyn StdError>> {
+    let data = secret_key.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let res = SecretKey::from_bytes(&data.unwrap());
+    if res.is_err() {
+        return Err(format!("invalid bls secret key: {:?}", res.err()).into());
+    }
+    Ok(res.unwrap())
+}
+
+pub fn get_public_key(secret_key: &SecretKey) -> HexString {
+    HexString::from_vec(secret_key.sk_to_pk().compress().to_vec())
+}
+
+pub fn sign(secret_key: &SecretKey, msg: &[u8]) -> HexString {
+    HexString::from_vec(secret_key.sign(msg, BLS_DST, &[]).compress().to_vec())
+}
+
+pub fn prove_possession(secret_key: &SecretKey) -> HexString {
+    let public_key = secret_key.sk_to_pk().compress();
+    HexString::from_vec(
+        secret_key
+            .sign(&public_key, BLS_POP_DST, &[])
+            .compress()
+            .to_vec(),
+    )
+}
+
+pub fn verify_possession(
+    public_key: &HexString,
+    proof: &HexString,
+) -> Result<bool, Box<dyn StdError>> {
+    let data = public_key.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let pk_bytes = data.unwrap();
+    let res = PublicKey::uncompress(&pk_bytes);
+    if res.is_err() {
+        return Err(format!("invalid bls public key: {:?}", res.err()).into());
+    }
+    let pk = res.unwrap();
+    let data = proof.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let res = Signature::uncompress(&data.unwrap());
+    if res.is_err() {
+        return Err(format!("invalid proof of possession: {:?}", res.err()).into());
+    }
+    let sig = res.unwrap();
+    Ok(sig.verify(true, &pk_bytes, BLS_POP_DST, &[], &pk, true) == BLST_ERROR::BLST_SUCCESS)
+}
+
+pub fn aggregate_signatures(sigs: &Vec<HexString>) -> Result<HexString, Box<dyn StdError>> {
+    let mut decoded_sigs: Vec<Signature> = Vec::new();
+    for sig in sigs {
+        let data = sig.to_vec();
+        if data.is_err() {
+            return Err(format!("{:?}", data.err()).into());
+        }
+        let res = Signature::uncompress(&data.unwrap());
+        if res.is_err() {
+            return Err(format!("invalid bls signature: {:?}", res.err()).into());
+        }
+        decoded_sigs.push(res.unwrap());
+    }
+    let sig_refs: Vec<&Signature> = decoded_sigs.iter().collect();
+    let res = AggregateSignature::aggregate(&sig_refs, true);
+    if res.is_err() {
+        return Err(format!("aggregating signatures: {:?}", res.err()).into());
+    }
+    Ok(HexString::from_vec(
+        res.unwrap().to_signature().compress().to_vec(),
+    ))
+}
+
+// all validators signed the same msg, so single pairing check against aggregated public key
+pub fn verify_aggregated_signature(
+    msg: &[u8],
+    aggregated_sig: &HexString,
+    public_keys: &Vec<HexString>,
+) -> Result<bool, Box<dyn StdError>> {
+    let data = aggregated_sig.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let res = Signature::uncompress(&data.unwrap());
+    if res.is_err() {
+        return Err(format!("invalid bls signature: {:?}", res.err()).into());
+    }
+    let sig = res.unwrap();
+
+    let mut pks: Vec<PublicKey> = Vec::new();
+    for public_key in public_keys {
+        let data = public_key.to_vec();
+        if data.is_err() {
+            return Err(format!("{:?}", data.err()).into());
+        }
+        let res = PublicKey::uncompress(&data.unwrap());
+        if res.is_err() {
+            return Err(format!("invalid bls public key: {:?}", res.err()).into());
+        }
+        pks.push(res.unwrap());
+    }
+    let pk_refs: Vec<&PublicKey> = pks.iter().collect();
+    Ok(sig.fast_aggregate_verify(true, msg, BLS_DST, &pk_refs) == BLST_ERROR::BLST_SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blst::{
+        blst_p1, blst_p1_add_or_double, blst_p1_affine, blst_p1_cneg, blst_p1_from_affine,
+        blst_p1_to_affine,
+    };
+
+    const MSG: &[u8] = b"withdraw digest";
+
+    fn get_keys(count: usize) -> Vec<SecretKey> {
+        (0..count).map(|_| generate_secret_key().unwrap()).collect()
+    }
+
+    // public key which cancels victim key out of aggregated key, attacker - victim
+    fn get_rogue_public_key(attacker: &SecretKey, victim: &SecretKey) -> HexString {
+        let attacker_pk: blst_p1_affine = attacker.sk_to_pk().into();
+        let victim_pk: blst_p1_affine = victim.sk_to_pk().into();
+        let mut rogue_pk = blst_p1_affine::default();
+        unsafe {
+            let mut attacker_point = blst_p1::default();
+            blst_p1_from_affine(&mut attacker_point, &attacker_pk);
+            let mut victim_point = blst_p1::default();
+            blst_p1_from_affine(&mut victim_point, &victim_pk);
+            blst_p1_cneg(&mut victim_point, true);
+            let mut rogue_point = blst_p1::default();
+            blst_p1_add_or_double(&mut rogue_point, &attacker_point, &victim_point);
+            blst_p1_to_affine(&mut rogue_pk, &rogue_point);
+        }
+        HexString::from_vec(PublicKey::from(rogue_pk).compress().to_vec())
+    }
+
+    #[test]
+    fn loads_serialized_secret_key() {
+        let secret_key = generate_secret_key().unwrap();
+        let loaded =
+            load_secret_key(&HexString::from_vec(secret_key.serialize().to_vec())).unwrap();
+        assert_eq!(get_public_key(&loaded), get_public_key(&secret_key));
+        assert!(load_secret_key(&HexString::from_vec(vec![0u8; 32])).is_err());
+    }
+
+    #[test]
+    fn verifies_proof_of_possession() {
+        let keys = get_keys(2usize);
+        let public_key = get_public_key(&keys[0]);
+        assert!(verify_possession(&public_key, &prove_possession(&keys[0])).unwrap());
+        // proof of other key
+        assert!(!verify_possession(&public_key, &prove_possession(&keys[1])).unwrap());
+        // signature over public key with signing dst instead of pop dst
+        let public_key_bytes = keys[0].sk_to_pk().compress();
+        assert!(!verify_possession(&public_key, &sign(&keys[0], &public_key_bytes)).unwrap());
+        // tampered proof
+        let mut proof = prove_possession(&keys[0]).to_vec().unwrap();
+        proof[10] ^= 1u8;
+        assert!(!verify_possession(&public_key, &HexString::from_vec(proof)).unwrap_or(false));
+    }
+
+    #[test]
+    fn verifies_aggregated_signature_of_exact_signers() {
+        let keys = get_keys(3usize);
+        let public_keys = keys.iter().map(get_public_key).collect::<Vec<HexString>>();
+        let sigs = keys
+            .iter()
+            .map(|key| sign(key, MSG))
+            .collect::<Vec<HexString>>();
+
+        let aggregated = aggregate_signatures(&sigs).unwrap();
+        assert!(verify_aggregated_signature(MSG, &aggregated, &public_keys).unwrap());
+        assert!(!verify_aggregated_signature(b"other digest", &aggregated, &public_keys).unwrap());
+
+        // signer missing from aggregated signature
+        let aggregated = aggregate_signatures(&sigs[..2].to_vec()).unwrap();
+        assert!(!verify_aggregated_signature(MSG, &aggregated, &public_keys).unwrap());
+        // signer not in public keys
+        let aggregated = aggregate_signatures(&sigs).unwrap();
+        assert!(
+            !verify_aggregated_signature(MSG, &aggregated, &public_keys[..2].to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_rogue_public_key() {
+        let keys = get_keys(2usize);
+        let (victim, attacker) = (&keys[0], &keys[1]);
+        let rogue_public_key = get_rogue_public_key(attacker, victim);
+
+        // without proof of possession attacker alone passes as victim and rogue key signing together
+        let forged = aggregate_signatures(&vec![sign(attacker, MSG)]).unwrap();
+        assert!(verify_aggregated_signature(
+            MSG,
+            &forged,
+            &vec![get_public_key(victim), rogue_public_key.clone()]
+        )
+        .unwrap());
+
+        // attacker doesn't know secret of rogue key, so it can't prove possession of it
+        assert!(!verify_possession(&rogue_public_key, &prove_possession(attacker)).unwrap());
+        let rogue_sig = sign(attacker, &keys[1].sk_to_pk().compress());
+        assert!(!verify_possession(&rogue_public_key, &rogue_sig).unwrap());
+    }
+}
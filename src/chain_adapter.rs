@@ -0,0 +1,878 @@
+use crate::{
+    constants::{
+        BatchWithdrawRequest, Bech32Charset, Bech32MaxLength, ChainTypeCosmWasm, ChainTypeEvm,
+    },
+    proposal::{
+        get_crr_locked_event_payload_params, get_crr_message_event_payload_params,
+        get_crr_unlocked_event_payload_params,
+    },
+    types::{
+        Address, CrossChainWithdrawPayload, HexString, TxCrossChainMessageParams,
+        TxCrossChainReplyParams, TxCrossChainRequestParams,
+    },
+    utils::{
+        address_to_str, decode_crosschain_withdraw_payload, get_crosschain_batch_payload,
+        get_crosschain_withdraw_digest, get_crosschain_withdraw_payload,
+    },
+};
+use cosmwasm_std::{from_json, to_json_vec, Binary, Uint128};
+use ethers::{
+    types::{Signature, H256},
+    utils::keccak256,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, error::Error as StdError, str::FromStr};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ChainType {
+    #[default]
+    Evm,
+    CosmWasm,
+}
+
+impl ChainType {
+    pub fn from_u8(chain_type: u8) -> Result<ChainType, Box<dyn StdError>> {
+        match chain_type {
+            ChainTypeEvm => Ok(ChainType::Evm),
+            ChainTypeCosmWasm => Ok(ChainType::CosmWasm),
+            _ => Err(format!("unknown chain type {}", chain_type).into()),
+        }
+    }
+}
+
+// how digichain talks to gateway of a chain: address format, withdraw payload and signature scheme
+pub trait ChainAdapter {
+    fn chain_type(&self) -> ChainType;
+
+    // chain address to digichain address
+    fn parse_address(&self, address: &str) -> Result<Address, Box<dyn StdError>>;
+
+    // digichain address to chain address, lowercase as in token mapping of chain
+    fn format_address(&self, address: Address) -> String;
+
+    fn encode_withdraw_payload(
+        &self,
+        payload: &CrossChainWithdrawPayload,
+    ) -> Result<HexString, Box<dyn StdError>>;
+
+    fn decode_withdraw_payload(
+        &self,
+        payload: &HexString,
+    ) -> Result<CrossChainWithdrawPayload, Box<dyn StdError>>;
+
+    // merkle root of batched withdraws, each withdraw is paid with its proof or all in one call
+    fn encode_batch_payload(
+        &self,
+        root: H256,
+        src_nonces: &Vec<Uint128>,
+    ) -> Result<HexString, Box<dyn StdError>>;
+
+    // digest validators sign for withdraw, gateway verifies signatures against it
+    fn get_withdraw_digest(
+        &self,
+        src_chain_id: String,
+        dst_chain_id: String,
+        src_nonce: Uint128,
+        payload: &HexString,
+    ) -> Result<H256, Box<dyn StdError>>;
+
+    // validator signature in format gateway accepts
+    fn encode_signature(&self, signature: &Signature) -> Vec<u8>;
+
+    // data of inbound CrossChainRequest, in format gateway events of chain are relayed in
+    fn decode_locked_fund_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainRequestParams, Box<dyn StdError>>;
+
+    fn decode_reply_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainReplyParams, Box<dyn StdError>>;
+
+    fn decode_message_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainMessageParams, Box<dyn StdError>>;
+}
+
+// 20 byte stand-in of chain asset which doesn't fit digichain address, i.e. 32 byte cw20 contract
+// or native denom, chain adapter maps it back to asset
+pub fn get_asset_address(asset: &str) -> Address {
+    Address::from_slice(&keccak256(asset.as_bytes())[12..])
+}
+
+// abi encoded payload, eth signed message digest recovered with ecrecover
+#[derive(Clone, Debug, Default)]
+pub struct EvmAdapter;
+
+impl ChainAdapter for EvmAdapter {
+    fn chain_type(&self) -> ChainType {
+        ChainType::Evm
+    }
+
+    fn parse_address(&self, address: &str) -> Result<Address, Box<dyn StdError>> {
+        let res = Address::from_str(address);
+        if res.is_err() {
+            return Err(format!("invalid address {}: {:?}", address, res.err()).into());
+        }
+        Ok(res.unwrap())
+    }
+
+    fn format_address(&self, address: Address) -> String {
+        address_to_str(address).to_lowercase()
+    }
+
+    fn encode_withdraw_payload(
+        &self,
+        payload: &CrossChainWithdrawPayload,
+    ) -> Result<HexString, Box<dyn StdError>> {
+        let message = payload.message.to_vec();
+        if message.is_err() {
+            return Err(format!("{:?}", message.err()).into());
+        }
+        Ok(get_crosschain_withdraw_payload(
+            payload.request_type,
+            payload.tokens.clone(),
+            payload.amounts.clone(),
+            payload.depositor,
+            payload.recipient,
+            message.unwrap(),
+        ))
+    }
+
+    fn decode_withdraw_payload(
+        &self,
+        payload: &HexString,
+    ) -> Result<CrossChainWithdrawPayload, Box<dyn StdError>> {
+        decode_crosschain_withdraw_payload(payload)
+    }
+
+    fn encode_batch_payload(
+        &self,
+        root: H256,
+        src_nonces: &Vec<Uint128>,
+    ) -> Result<HexString, Box<dyn StdError>> {
+        Ok(get_crosschain_batch_payload(root, src_nonces))
+    }
+
+    fn get_withdraw_digest(
+        &self,
+        src_chain_id: String,
+        dst_chain_id: String,
+        src_nonce: Uint128,
+        payload: &HexString,
+    ) -> Result<H256, Box<dyn StdError>> {
+        get_crosschain_withdraw_digest(src_chain_id, dst_chain_id, src_nonce, payload)
+    }
+
+    // r || s || v
+    fn encode_signature(&self, signature: &Signature) -> Vec<u8> {
+        signature.to_vec()
+    }
+
+    fn decode_locked_fund_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainRequestParams, Box<dyn StdError>> {
+        get_crr_locked_event_payload_params(data.clone())
+    }
+
+    fn decode_reply_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainReplyParams, Box<dyn StdError>> {
+        get_crr_unlocked_event_payload_params(data.clone())
+    }
+
+    fn decode_message_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainMessageParams, Box<dyn StdError>> {
+        get_crr_message_event_payload_params(data.clone())
+    }
+}
+
+// json payload with bech32 addresses, gateway verifies sha256 digest with secp256k1_verify against
+// registered validator public keys.
+// 20 byte accounts are digichain addresses as is, 32 byte contracts and native denoms go through assets,
+// unregistered ones are rejected as their stand-in can't be formatted back
+#[derive(Clone, Debug, Default)]
+pub struct CosmWasmAdapter {
+    pub prefix: String, // bech32 human readable part of chain, i.e. osmo
+    pub assets: HashMap<Address, String>, // stand-in address -> asset registered in token mapping of chain
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct CosmWasmWithdrawPayload {
+    request_type: u8,
+    tokens: Vec<String>,
+    amounts: Vec<Uint128>,
+    depositor: String, // digichain address
+    recipient: String,
+    message: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct CosmWasmBatchPayload {
+    request_type: u8,
+    root: Binary, // keccak merkle root of withdraw msg hashes
+    src_nonces: Vec<Uint128>,
+}
+
+// inbound payloads, chain addresses are bech32 and digichain ones hex
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct CosmWasmLockedFundPayload {
+    src_chain_id: String,
+    dst_chain_id: String,
+    src_contract: String,
+    recipient: String, // digichain address
+    depositor: String,
+    tokens: Vec<String>,
+    amounts: Vec<Uint128>,
+    src_nonce: Uint128,
+    src_block_number: u64,
+    src_tx_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct CosmWasmReplyPayload {
+    src_chain_id: String,
+    dst_chain_id: String,
+    src_contract: String,
+    recipient: String,
+    depositor: String, // digichain address
+    tokens: Vec<String>,
+    amounts: Vec<Uint128>,
+    src_nonce: Uint128,
+    dst_nonce: Uint128,
+    dst_block_number: u64,
+    dst_tx_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct CosmWasmMessagePayload {
+    src_chain_id: String,
+    dst_chain_id: String,
+    src_contract: String,
+    sender: String,
+    handler: String, // digichain address
+    message: Binary,
+    src_nonce: Uint128,
+    src_block_number: u64,
+    src_tx_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct CosmWasmWithdrawMsg {
+    src_chain_id: String,
+    dst_chain_id: String,
+    src_nonce: Uint128,
+    payload: Binary,
+}
+
+impl CosmWasmAdapter {
+    pub fn new(prefix: String, assets: HashMap<Address, String>) -> CosmWasmAdapter {
+        CosmWasmAdapter { prefix, assets }
+    }
+
+    fn parse_addresses(&self, addresses: &Vec<String>) -> Result<Vec<Address>, Box<dyn StdError>> {
+        let mut parsed: Vec<Address> = Vec::new();
+        for address in addresses.iter() {
+            parsed.push(self.parse_address(address)?);
+        }
+        Ok(parsed)
+    }
+}
+
+fn parse_hex_address(address: &str, name: &str) -> Result<Address, Box<dyn StdError>> {
+    let res = Address::from_str(address);
+    if res.is_err() {
+        return Err(format!("invalid {}: {:?}", name, res.err()).into());
+    }
+    Ok(res.unwrap())
+}
+
+fn decode_json<T: for<'de> Deserialize<'de>>(data: &HexString) -> Result<T, Box<dyn StdError>> {
+    let data = data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let res = from_json::<T>(&data.unwrap());
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    Ok(res.unwrap())
+}
+
+impl ChainAdapter for CosmWasmAdapter {
+    fn chain_type(&self) -> ChainType {
+        ChainType::CosmWasm
+    }
+
+    fn parse_address(&self, address: &str) -> Result<Address, Box<dyn StdError>> {
+        // native denoms aren't bech32, only registered ones are known
+        if let Some((asset_address, _)) = self.assets.iter().find(|(_, asset)| *asset == address) {
+            return Ok(*asset_address);
+        }
+        let (prefix, data) = bech32_decode(address)?;
+        if prefix != self.prefix {
+            return Err(format!("address {} is not of prefix {}", address, self.prefix).into());
+        }
+        match data.len() {
+            20usize => Ok(Address::from_slice(&data)),
+            32usize => {
+                let asset_address = get_asset_address(&address.to_lowercase());
+                if !self.assets.contains_key(&asset_address) {
+                    return Err(format!("32 byte address {} is not registered", address).into());
+                }
+                Ok(asset_address)
+            }
+            _ => Err(format!("address {} is neither 20 nor 32 bytes", address).into()),
+        }
+    }
+
+    fn format_address(&self, address: Address) -> String {
+        if let Some(asset) = self.assets.get(&address) {
+            return asset.clone();
+        }
+        bech32_encode(&self.prefix, address.as_bytes())
+    }
+
+    fn encode_withdraw_payload(
+        &self,
+        payload: &CrossChainWithdrawPayload,
+    ) -> Result<HexString, Box<dyn StdError>> {
+        let message = payload.message.to_vec();
+        if message.is_err() {
+            return Err(format!("{:?}", message.err()).into());
+        }
+        let res = to_json_vec(&CosmWasmWithdrawPayload {
+            request_type: payload.request_type,
+            tokens: payload
+                .tokens
+                .iter()
+                .map(|token| self.format_address(*token))
+                .collect(),
+            amounts: payload.amounts.clone(),
+            depositor: address_to_str(payload.depositor),
+            recipient: self.format_address(payload.recipient),
+            message: Binary::from(message.unwrap()),
+        });
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        Ok(HexString::from_vec(res.unwrap()))
+    }
+
+    fn decode_withdraw_payload(
+        &self,
+        payload: &HexString,
+    ) -> Result<CrossChainWithdrawPayload, Box<dyn StdError>> {
+        let data = payload.to_vec();
+        if data.is_err() {
+            return Err(format!("{:?}", data.err()).into());
+        }
+        let res = from_json::<CosmWasmWithdrawPayload>(&data.unwrap());
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        let payload = res.unwrap();
+        Ok(CrossChainWithdrawPayload {
+            request_type: payload.request_type,
+            tokens: self.parse_addresses(&payload.tokens)?,
+            amounts: payload.amounts,
+            depositor: parse_hex_address(&payload.depositor, "depositor")?,
+            recipient: self.parse_address(&payload.recipient)?,
+            message: HexString::from_vec(payload.message.to_vec()),
+        })
+    }
+
+    fn encode_batch_payload(
+        &self,
+        root: H256,
+        src_nonces: &Vec<Uint128>,
+    ) -> Result<HexString, Box<dyn StdError>> {
+        let res = to_json_vec(&CosmWasmBatchPayload {
+            request_type: BatchWithdrawRequest,
+            root: Binary::from(root.as_bytes()),
+            src_nonces: src_nonces.clone(),
+        });
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        Ok(HexString::from_vec(res.unwrap()))
+    }
+
+    // sha256(json(src_chain_id, dst_chain_id, src_nonce, payload))
+    fn get_withdraw_digest(
+        &self,
+        src_chain_id: String,
+        dst_chain_id: String,
+        src_nonce: Uint128,
+        payload: &HexString,
+    ) -> Result<H256, Box<dyn StdError>> {
+        let payload = payload.to_vec();
+        if payload.is_err() {
+            return Err(format!("{:?}", payload.err()).into());
+        }
+        let res = to_json_vec(&CosmWasmWithdrawMsg {
+            src_chain_id,
+            dst_chain_id,
+            src_nonce,
+            payload: Binary::from(payload.unwrap()),
+        });
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        Ok(H256::from_slice(&Sha256::digest(res.unwrap())))
+    }
+
+    // r || s, secp256k1_verify takes no recovery id
+    fn encode_signature(&self, signature: &Signature) -> Vec<u8> {
+        signature.to_vec()[..64].to_vec()
+    }
+
+    fn decode_locked_fund_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainRequestParams, Box<dyn StdError>> {
+        let payload = decode_json::<CosmWasmLockedFundPayload>(data)?;
+        Ok(TxCrossChainRequestParams {
+            src_chain_id: payload.src_chain_id,
+            dst_chain_id: payload.dst_chain_id,
+            src_contract: self.parse_address(&payload.src_contract)?,
+            recipient: parse_hex_address(&payload.recipient, "recipient")?,
+            depositor: self.parse_address(&payload.depositor)?,
+            tokens: self.parse_addresses(&payload.tokens)?,
+            amounts: payload.amounts,
+            src_nonce: payload.src_nonce,
+            src_block_number: payload.src_block_number,
+            src_tx_hash: payload.src_tx_hash,
+        })
+    }
+
+    fn decode_reply_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainReplyParams, Box<dyn StdError>> {
+        let payload = decode_json::<CosmWasmReplyPayload>(data)?;
+        Ok(TxCrossChainReplyParams {
+            src_chain_id: payload.src_chain_id,
+            dst_chain_id: payload.dst_chain_id,
+            src_contract: self.parse_address(&payload.src_contract)?,
+            recipient: self.parse_address(&payload.recipient)?,
+            depositor: parse_hex_address(&payload.depositor, "depositor")?,
+            tokens: self.parse_addresses(&payload.tokens)?,
+            amounts: payload.amounts,
+            src_nonce: payload.src_nonce,
+            dst_nonce: payload.dst_nonce,
+            dst_block_number: payload.dst_block_number,
+            dst_tx_hash: payload.dst_tx_hash,
+        })
+    }
+
+    fn decode_message_payload(
+        &self,
+        data: &HexString,
+    ) -> Result<TxCrossChainMessageParams, Box<dyn StdError>> {
+        let payload = decode_json::<CosmWasmMessagePayload>(data)?;
+        Ok(TxCrossChainMessageParams {
+            src_chain_id: payload.src_chain_id,
+            dst_chain_id: payload.dst_chain_id,
+            src_contract: self.parse_address(&payload.src_contract)?,
+            sender: self.parse_address(&payload.sender)?,
+            handler: parse_hex_address(&payload.handler, "handler")?,
+            message: HexString::from_vec(payload.message.to_vec()),
+            src_nonce: payload.src_nonce,
+            src_block_number: payload.src_block_number,
+            src_tx_hash: payload.src_tx_hash,
+        })
+    }
+}
+
+// gateway address tells bech32 prefix of cosmwasm chains, assets are the ones registered for chain
+// along with gateway contract itself
+pub fn new_chain_adapter(
+    chain_type: u8,
+    contract_address: &str,
+    mut assets: HashMap<Address, String>,
+) -> Result<Box<dyn ChainAdapter>, Box<dyn StdError>> {
+    match ChainType::from_u8(chain_type)? {
+        ChainType::Evm => Ok(Box::new(EvmAdapter)),
+        ChainType::CosmWasm => {
+            let (prefix, data) = bech32_decode(contract_address)?;
+            if data.len() == 32usize {
+                let contract_address = contract_address.to_lowercase();
+                assets.insert(get_asset_address(&contract_address), contract_address);
+            }
+            Ok(Box::new(CosmWasmAdapter::new(prefix, assets)))
+        }
+    }
+}
+
+pub fn bech32_encode(prefix: &str, data: &[u8]) -> String {
+    // regrouping 8 bit to 5 bit with padding never fails
+    let mut values = convert_bits(data, 8u32, 5u32, true).unwrap_or_default();
+    let mut checksum_input = bech32_expand_prefix(prefix);
+    checksum_input.extend(values.iter());
+    checksum_input.extend([0u8; 6].iter());
+    let polymod = bech32_polymod(&checksum_input) ^ 1u32;
+    for idx in 0..6u32 {
+        values.push(((polymod >> (5u32 * (5u32 - idx))) & 31u32) as u8);
+    }
+    let charset = Bech32Charset.as_bytes();
+    let encoded = values
+        .iter()
+        .map(|value| charset[*value as usize] as char)
+        .collect::<String>();
+    format!("{}1{}", prefix, encoded)
+}
+
+pub fn bech32_decode(address: &str) -> Result<(String, Vec<u8>), Box<dyn StdError>> {
+    if address.len() > Bech32MaxLength {
+        return Err(format!("bech32 address {} is too long", address).into());
+    }
+    if address.to_lowercase() != address && address.to_uppercase() != address {
+        return Err(format!("mixed case bech32 address {}", address).into());
+    }
+    let address = address.to_lowercase();
+    let res = address.rfind('1');
+    if res.is_none() {
+        return Err(format!("bech32 separator missing in {}", address).into());
+    }
+    let separator = res.unwrap();
+    if separator == 0usize || separator + 7usize > address.len() {
+        return Err(format!("invalid bech32 address {}", address).into());
+    }
+    let prefix = address[..separator].to_string();
+    if prefix.bytes().any(|c| c < 33u8 || c > 126u8) {
+        return Err(format!("invalid bech32 prefix {}", prefix).into());
+    }
+    let mut values: Vec<u8> = Vec::new();
+    for c in address[separator + 1..].chars() {
+        let res = Bech32Charset.find(c);
+        if res.is_none() {
+            return Err(format!("invalid bech32 character {}", c).into());
+        }
+        values.push(res.unwrap() as u8);
+    }
+    let mut checksum_input = bech32_expand_prefix(&prefix);
+    checksum_input.extend(values.iter());
+    if bech32_polymod(&checksum_input) != 1u32 {
+        return Err(format!("invalid bech32 checksum {}", address).into());
+    }
+    let data = convert_bits(&values[..values.len() - 6usize], 5u32, 8u32, false)?;
+    Ok((prefix, data))
+}
+
+fn bech32_expand_prefix(prefix: &str) -> Vec<u8> {
+    let mut expanded = prefix.bytes().map(|c| c >> 5).collect::<Vec<u8>>();
+    expanded.push(0u8);
+    expanded.extend(prefix.bytes().map(|c| c & 31u8));
+    expanded
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    let generator = [
+        0x3b6a57b2u32,
+        0x26508e6du32,
+        0x1ea119fau32,
+        0x3d4233ddu32,
+        0x2a1462b3u32,
+    ];
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ (*value as u32);
+        for idx in 0..5usize {
+            if (top >> idx) & 1u32 == 1u32 {
+                checksum ^= generator[idx];
+            }
+        }
+    }
+    checksum
+}
+
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max_value = (1u32 << to) - 1u32;
+    let max_acc = (1u32 << (from + to - 1u32)) - 1u32;
+    let mut converted: Vec<u8> = Vec::new();
+    for value in data {
+        let value = *value as u32;
+        if value >> from != 0u32 {
+            return Err(format!("invalid {} bit value {}", from, value).into());
+        }
+        acc = ((acc << from) | value) & max_acc;
+        bits = bits + from;
+        while bits >= to {
+            bits = bits - to;
+            converted.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+    if pad {
+        if bits > 0u32 {
+            converted.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_value) != 0u32 {
+        return Err(format!("invalid bech32 padding").into());
+    }
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::UnLockedWithdrawRequest;
+
+    // checksum only, data of bip-173 vectors isn't always whole bytes
+    fn has_valid_checksum(address: &str) -> bool {
+        let address = address.to_lowercase();
+        let separator = address.rfind('1').unwrap();
+        let mut checksum_input = bech32_expand_prefix(&address[..separator]);
+        checksum_input.extend(
+            address[separator + 1..]
+                .chars()
+                .map(|c| Bech32Charset.find(c).unwrap() as u8),
+        );
+        bech32_polymod(&checksum_input) == 1u32
+    }
+
+    fn get_adapter() -> (CosmWasmAdapter, String, String) {
+        let cw20 = bech32_encode("osmo", &[7u8; 32]);
+        let denom = "uosmo".to_string();
+        let mut assets = HashMap::new();
+        assets.insert(get_asset_address(&cw20), cw20.clone());
+        assets.insert(get_asset_address(&denom), denom.clone());
+        (
+            CosmWasmAdapter::new("osmo".to_string(), assets),
+            cw20,
+            denom,
+        )
+    }
+
+    #[test]
+    fn accepts_bip173_valid_strings() {
+        for address in [
+            "A12UEL5L",
+            "a12uel5l",
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            "?1ezyfcl",
+        ] {
+            assert!(has_valid_checksum(address), "{}", address);
+        }
+        assert_eq!(
+            bech32_decode("A12UEL5L").unwrap(),
+            ("a".to_string(), vec![])
+        );
+        assert_eq!(
+            bech32_decode("11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j")
+                .unwrap(),
+            ("1".to_string(), vec![0u8; 51])
+        );
+        assert_eq!(
+            bech32_decode("?1ezyfcl").unwrap(),
+            ("?".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn rejects_bip173_invalid_strings() {
+        for address in [
+            "\u{20}1nwldj5",
+            "\u{7f}1axkwrx",
+            "\u{80}1eym55h",
+            "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+            "pzry9x0s0muk",
+            "1pzry9x0s0muk",
+            "x1b4n0q5v",
+            "li1dgmt3",
+            "de1lg7wt\u{ff}",
+            "A1G7SGD8",
+            "10a06t8",
+            "1qzzfhee",
+            "a12UEL5L",
+        ] {
+            assert!(bech32_decode(address).is_err(), "{}", address);
+        }
+    }
+
+    #[test]
+    fn converts_bits() {
+        assert_eq!(
+            convert_bits(&[0xffu8], 8u32, 5u32, true).unwrap(),
+            vec![31u8, 28u8]
+        );
+        assert_eq!(
+            convert_bits(&[0x00u8, 0x01u8], 8u32, 5u32, true).unwrap(),
+            vec![0u8, 0u8, 0u8, 16u8]
+        );
+        assert_eq!(
+            convert_bits(&[31u8, 28u8], 5u32, 8u32, false).unwrap(),
+            vec![0xffu8]
+        );
+        // non zero padding
+        assert!(convert_bits(&[31u8, 29u8], 5u32, 8u32, false).is_err());
+        // padding of 5 bits or more
+        assert!(convert_bits(&[0u8; 6], 5u32, 8u32, false).is_err());
+        // value wider than 5 bits
+        assert!(convert_bits(&[32u8], 5u32, 8u32, false).is_err());
+
+        let data = (0u8..=255u8).collect::<Vec<u8>>();
+        let values = convert_bits(&data, 8u32, 5u32, true).unwrap();
+        assert_eq!(convert_bits(&values, 5u32, 8u32, false).unwrap(), data);
+    }
+
+    #[test]
+    fn parses_registered_addresses_only() {
+        let (adapter, cw20, denom) = get_adapter();
+        let account = Address::from_low_u64_be(9u64);
+        let address = bech32_encode("osmo", account.as_bytes());
+        assert_eq!(adapter.parse_address(&address).unwrap(), account);
+        assert_eq!(
+            adapter.parse_address(&address.to_uppercase()).unwrap(),
+            account
+        );
+        assert_eq!(adapter.format_address(account), address);
+
+        let cw20_address = adapter.parse_address(&cw20).unwrap();
+        assert_eq!(adapter.format_address(cw20_address), cw20);
+        let denom_address = adapter.parse_address(&denom).unwrap();
+        assert_eq!(adapter.format_address(denom_address), denom);
+
+        // 32 byte contract not registered for chain
+        assert!(adapter
+            .parse_address(&bech32_encode("osmo", &[8u8; 32]))
+            .is_err());
+        assert!(adapter
+            .parse_address(&bech32_encode("juno", account.as_bytes()))
+            .is_err());
+        assert!(adapter
+            .parse_address(&bech32_encode("osmo", &[9u8; 24]))
+            .is_err());
+        assert!(adapter.parse_address("uatom").is_err());
+    }
+
+    #[test]
+    fn knows_gateway_contract() {
+        let gateway = bech32_encode("osmo", &[1u8; 32]);
+        let adapter = new_chain_adapter(ChainTypeCosmWasm, &gateway, HashMap::new()).unwrap();
+        let gateway_address = adapter.parse_address(&gateway).unwrap();
+        assert_eq!(adapter.format_address(gateway_address), gateway);
+    }
+
+    #[test]
+    fn round_trips_withdraw_payload() {
+        let (adapter, cw20, denom) = get_adapter();
+        let payload = CrossChainWithdrawPayload {
+            request_type: UnLockedWithdrawRequest,
+            tokens: vec![
+                adapter.parse_address(&cw20).unwrap(),
+                adapter.parse_address(&denom).unwrap(),
+            ],
+            amounts: vec![Uint128::from(100u128), Uint128::from(5u128)],
+            depositor: Address::from_low_u64_be(4u64),
+            recipient: Address::from_low_u64_be(5u64),
+            message: HexString::from_vec(vec![1u8, 2u8]),
+        };
+        let encoded = adapter.encode_withdraw_payload(&payload).unwrap();
+        let json = decode_json::<CosmWasmWithdrawPayload>(&encoded).unwrap();
+        assert_eq!(json.tokens, vec![cw20, denom]);
+        assert_eq!(
+            json.recipient,
+            bech32_encode("osmo", Address::from_low_u64_be(5u64).as_bytes())
+        );
+        assert_eq!(adapter.decode_withdraw_payload(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn decodes_inbound_payloads() {
+        let (adapter, cw20, denom) = get_adapter();
+        let gateway = bech32_encode("osmo", &[1u8; 32]);
+        let adapter = CosmWasmAdapter::new(adapter.prefix, {
+            let mut assets = adapter.assets;
+            assets.insert(get_asset_address(&gateway), gateway.clone());
+            assets
+        });
+        let depositor = bech32_encode("osmo", Address::from_low_u64_be(4u64).as_bytes());
+        let recipient = Address::from_low_u64_be(5u64);
+        let locked_fund = CosmWasmLockedFundPayload {
+            src_chain_id: "osmosis-1".to_string(),
+            dst_chain_id: "digichain".to_string(),
+            src_contract: gateway.clone(),
+            recipient: address_to_str(recipient),
+            depositor: depositor.clone(),
+            tokens: vec![cw20.clone(), denom],
+            amounts: vec![Uint128::from(100u128), Uint128::from(5u128)],
+            src_nonce: Uint128::from(3u128),
+            src_block_number: 10u64,
+            src_tx_hash: "ABCD".to_string(),
+        };
+        let data = HexString::from_vec(to_json_vec(&locked_fund).unwrap());
+        let params = adapter.decode_locked_fund_payload(&data).unwrap();
+        assert_eq!(params.src_contract, get_asset_address(&gateway));
+        assert_eq!(params.recipient, recipient);
+        assert_eq!(params.depositor, Address::from_low_u64_be(4u64));
+        assert_eq!(
+            params
+                .tokens
+                .iter()
+                .map(|token| adapter.format_address(*token))
+                .collect::<Vec<String>>(),
+            locked_fund.tokens
+        );
+        assert_eq!(params.amounts, locked_fund.amounts);
+        assert_eq!(params.src_nonce, locked_fund.src_nonce);
+
+        // unregistered token can't be refunded back, so request is rejected
+        let mut unregistered = locked_fund.clone();
+        unregistered.tokens[0] = bech32_encode("osmo", &[8u8; 32]);
+        let data = HexString::from_vec(to_json_vec(&unregistered).unwrap());
+        assert!(adapter.decode_locked_fund_payload(&data).is_err());
+
+        let reply = CosmWasmReplyPayload {
+            src_chain_id: "digichain".to_string(),
+            dst_chain_id: "osmosis-1".to_string(),
+            src_contract: gateway.clone(),
+            recipient: depositor,
+            depositor: address_to_str(recipient),
+            tokens: vec![cw20],
+            amounts: vec![Uint128::from(100u128)],
+            src_nonce: Uint128::from(3u128),
+            dst_nonce: Uint128::from(4u128),
+            dst_block_number: 11u64,
+            dst_tx_hash: "EF01".to_string(),
+        };
+        let data = HexString::from_vec(to_json_vec(&reply).unwrap());
+        let params = adapter.decode_reply_payload(&data).unwrap();
+        assert_eq!(params.recipient, Address::from_low_u64_be(4u64));
+        assert_eq!(params.depositor, recipient);
+        assert_eq!(params.dst_nonce, Uint128::from(4u128));
+
+        let message = CosmWasmMessagePayload {
+            src_chain_id: "osmosis-1".to_string(),
+            dst_chain_id: "digichain".to_string(),
+            src_contract: gateway,
+            sender: bech32_encode("osmo", Address::from_low_u64_be(6u64).as_bytes()),
+            handler: address_to_str(Address::from_low_u64_be(7u64)),
+            message: Binary::from(vec![1u8]),
+            src_nonce: Uint128::from(5u128),
+            src_block_number: 12u64,
+            src_tx_hash: "2345".to_string(),
+        };
+        let data = HexString::from_vec(to_json_vec(&message).unwrap());
+        let params = adapter.decode_message_payload(&data).unwrap();
+        assert_eq!(params.sender, Address::from_low_u64_be(6u64));
+        assert_eq!(params.handler, Address::from_low_u64_be(7u64));
+        assert_eq!(params.message, HexString::from_vec(vec![1u8]));
+        assert!(adapter
+            .decode_locked_fund_payload(&HexString::from_vec(b"{}".to_vec()))
+            .is_err());
+    }
+}
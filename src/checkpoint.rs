@@ -0,0 +1,94 @@
+use crate::types::Address;
+use ethers::{
+    core::k256::ecdsa::SigningKey,
+    signers::{Signer, Wallet},
+    types::{Signature, H256},
+    utils::{hash_message, keccak256},
+};
+use router_wasm_bindings::ethabi::{encode, ethereum_types::U256, Token};
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+
+// finalized block of digichain signed by validators, anchored to contract on external chain
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CheckpointMsg {
+    pub chain_id: String,
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub state_root: H256,
+    pub sigs: Vec<Signature>,
+    pub signers: Vec<Address>, // signers[i] signed sigs[i]
+}
+
+// checkpoint posted on anchor chain, tx got mined there
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnchorRecord {
+    pub anchor_chain_id: String,
+    pub block_number: u64, // anchored digichain height
+    pub block_hash: H256,
+    pub state_root: H256,
+    pub tx_hash: H256,
+    pub anchor_block_number: u64, // block of anchor chain tx got mined in
+}
+
+impl CheckpointMsg {
+    pub fn new(
+        chain_id: String,
+        block_number: u64,
+        block_hash: H256,
+        state_root: H256,
+    ) -> CheckpointMsg {
+        CheckpointMsg {
+            chain_id,
+            block_number,
+            block_hash,
+            state_root,
+            sigs: vec![],
+            signers: vec![],
+        }
+    }
+
+    // keccak256(abi.encode(chain_id, block_number, block_hash, state_root)) with eth signed message prefix
+    pub fn get_digest(&self) -> H256 {
+        let edata = encode(&vec![
+            Token::String(self.chain_id.clone()),
+            Token::Uint(U256::from(self.block_number)),
+            Token::FixedBytes(self.block_hash.as_bytes().to_vec()),
+            Token::FixedBytes(self.state_root.as_bytes().to_vec()),
+        ]);
+        hash_message(keccak256(edata))
+    }
+
+    pub fn sign(&mut self, wallet: &Wallet<SigningKey>) -> Result<Signature, Box<dyn StdError>> {
+        if self.signers.contains(&wallet.address()) {
+            return Err(format!("already signed").into());
+        }
+        let res = wallet.sign_hash(self.get_digest());
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        let signature = res.unwrap();
+        self.sigs.push(signature);
+        self.signers.push(wallet.address());
+        Ok(signature)
+    }
+
+    // returns distinct signers whose signature is valid
+    pub fn verify_signatures(&self) -> Result<Vec<Address>, Box<dyn StdError>> {
+        if self.sigs.len() != self.signers.len() {
+            return Err(format!("sigs and signers length mismatch").into());
+        }
+        let digest = self.get_digest();
+        let mut valid_signers: Vec<Address> = Vec::new();
+        for idx in 0..self.sigs.len() {
+            if valid_signers.contains(&self.signers[idx]) {
+                continue;
+            }
+            let res = self.sigs[idx].recover(digest);
+            if res.is_ok() && res.unwrap() == self.signers[idx] {
+                valid_signers.push(self.signers[idx]);
+            }
+        }
+        Ok(valid_signers)
+    }
+}
@@ -4,3 +4,23 @@ pub const UnLockedFailedRequest: u8 = 2u8; // unlocking blocked fund on other ch
 
 // 1 -> withdraw
 // 2 -> ack received
+
+pub const CrossChainRequestTimeout: u64 = 20u64; // no of blocks assigned validator gets to broadcast a withdraw before it is reassigned
+pub const MaxInboundNonceGap: u128 = 1000u128; // inbound requests buffered at most this far ahead of last processed nonce
+pub const MessageRequest: u8 = 3u8; // arbitrary message to handler on other chain, no funds attached
+pub const BatchWithdrawRequest: u8 = 4u8; // withdraws of many transfers to same chain, signed once as merkle root of them
+pub const MaxTrackedHeaders: usize = 10000usize; // src chain headers kept by light client per chain, oldest dropped first
+pub const LockedFundEventSignature: &str =
+    "LockedFund(string,address,address,address[],uint256[],uint256)"; // event emitted by gateway when user locks funds
+pub const RelayerPollInterval: u64 = 5u64; // seconds between two polls of gateway events
+pub const RelayerConfirmations: u64 = 3u64; // blocks behind head a gateway event is relayed at
+pub const RelayerMaxBlockRange: u64 = 1000u64; // max blocks of gateway events fetched in one poll
+pub const ChainTypeEvm: u8 = 0u8; // gateway on evm chain, abi encoded payload
+pub const ChainTypeCosmWasm: u8 = 1u8; // gateway contract on cosmwasm chain, json payload and bech32 addresses
+pub const Bech32Charset: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+pub const Bech32MaxLength: usize = 90usize; // max length of bech32 string, bip-173
+pub const AnchorInterval: u64 = 100u64; // blocks between two checkpoints anchored to external chain
+pub const Eip712DomainName: &str = "DigiChain"; // domain of typed data signed by wallets
+pub const Eip712DomainVersion: &str = "1";
+pub const MaxQueuedTxsPerAccount: usize = 64usize; // future nonce txs of an account kept in mempool until nonce gap is filled
+pub const MaxQueuedTxAge: u64 = 600u64; // seconds future nonce tx waits in mempool for its nonce gap to be filled
@@ -1,12 +1,21 @@
 use crate::{
-    types::{Address, ContractConfig},
-    utils::address_to_str,
+    chain_adapter::{
+        bech32_decode, get_asset_address, new_chain_adapter, ChainAdapter, EvmAdapter,
+    },
+    checkpoint::{AnchorRecord, CheckpointMsg},
+    constants::{CrossChainRequestTimeout, MaxInboundNonceGap, MaxTrackedHeaders},
+    light_client::BlockHeader,
+    message::MessageHandler,
+    proposal::Proposal,
+    types::{Address, BatchConfig, ContractConfig, FeeConfig, HexString, RateLimitConfig, TokenId},
+    utils::get_crosschain_withdraw_msg_hash,
 };
 use cosmwasm_std::Uint128;
 use serde::{Deserialize, Serialize};
 use std::error::Error as StdError;
+use std::str::FromStr;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::{Arc, RwLock},
 };
 
@@ -22,7 +31,188 @@ pub struct CrossChainExecutionResult {
     pub src_tx_hash: String,
     pub dst_tx_hash: Option<String>,
     pub ack_tx_hash: Option<String>, // exist in case of failure during minting funds here
-                                     //TODO: result or status of cross chain request can be added
+    #[serde(default)]
+    pub error: Option<String>, // reason of failure, if any
+    #[serde(default)]
+    pub dst_chain_id: Option<String>,
+    #[serde(default)]
+    pub payload: Option<HexString>, // withdraw payload, abi encoded same as for outbound requests
+    #[serde(default)]
+    pub status_history: Vec<(CrossChainRequestStatus, u64)>, // status, block number at which request moved to it
+    #[serde(default)]
+    pub refund_nonce: Option<Uint128>, // nonce of request unlocking not minted funds on src chain
+    #[serde(default)]
+    pub refund_of: Option<(String, Uint128)>, // (src_chain_id,nonce) of inbound request this refund belongs to
+    #[serde(default)]
+    pub tokens: Vec<CrossChainTokenResult>, // outcome of each token of inbound request
+    #[serde(default)]
+    pub fees: Vec<CrossChainFee>, // fees of outbound transfer, escrowed till ack
+    #[serde(default)]
+    pub proven: bool, // inbound request came with receipt proof verified by light client
+    #[serde(default)]
+    pub batch_nonce: Option<Uint128>, // nonce of batch withdraw is sealed into
+    #[serde(default)]
+    pub batch_entries: Vec<Uint128>, // nonces of withdraws in batch, in order of merkle leaves
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum CrossChainTokenStatus {
+    Minted,
+    Delayed,      // over rate limit, minted once window passes
+    Unregistered, // not registered here, will be unlocked back on src chain
+    Dust,         // loses precision in digichain decimals, will be unlocked back on src chain
+    Refunded,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrossChainTokenResult {
+    pub token: Address, // token address on src chain
+    pub amount: Uint128,
+    pub status: CrossChainTokenStatus,
+}
+
+impl CrossChainExecutionResult {
+    pub fn new(src_tx_hash: String) -> CrossChainExecutionResult {
+        CrossChainExecutionResult {
+            src_tx_hash,
+            dst_tx_hash: None,
+            ack_tx_hash: None,
+            refund_nonce: None,
+            refund_of: None,
+            tokens: vec![],
+            error: None,
+            dst_chain_id: None,
+            payload: None,
+            status_history: vec![],
+            fees: vec![],
+            proven: false,
+            batch_nonce: None,
+            batch_entries: vec![],
+        }
+    }
+
+    pub fn get_status(&self) -> Option<CrossChainRequestStatus> {
+        self.status_history.last().map(|(status, _)| status.clone())
+    }
+
+    pub fn update_status(&mut self, status: CrossChainRequestStatus, block_number: u64) {
+        self.status_history.push((status, block_number));
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum CrossChainRequestStatus {
+    Initiated,           // outbound request created on this chain
+    Batched,             // withdraw sealed into batch, executed along with it
+    Attested,            // validators signed withdraw msg
+    AssignedToValidator, // validator is responsible to execute it on dst chain, again on reassignment
+    Broadcasted,         // validator relayed it
+    ExecutedOnDst,       // executed on dst chain, for inbound request dst chain is this chain
+    Acked,               // reply of execution received here
+    Failed,              // proposal reverted or some funds couldn't be minted
+    Refunded,            // not minted funds unlocked back on src chain
+}
+
+impl CrossChainRequestStatus {
+    // nothing can happen to request after these, refund of failed request is tracked on its own
+    pub fn is_final(&self) -> bool {
+        *self == CrossChainRequestStatus::Acked
+            || *self == CrossChainRequestStatus::Refunded
+            || *self == CrossChainRequestStatus::Failed
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrossChainFee {
+    pub token_id: TokenId,
+    pub amount: Uint128,
+    #[serde(default)]
+    pub paid_by: Address, // sender of transfer, refunded if withdraw fails
+    pub paid_to: Option<Address>, // validator credited once request is acked
+    #[serde(default)]
+    pub refunded: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrossChainAssignment {
+    pub validator: Address,
+    pub assigned_at: u64,
+    pub deadline: u64, // block number till which validator should broadcast it
+    pub reassignments: Vec<(Address, u64)>, // previous validator, block number at which it got reassigned
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RateLimitUsage {
+    pub account: Address,
+    pub amount: Uint128,
+    pub block_number: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DelayedMint {
+    pub src_chain_id: String,
+    pub src_nonce: Uint128,
+    pub idx: usize, // index of token in request
+    pub token_id: TokenId,
+    pub recipient: Address,
+    pub amount: Uint128,
+    pub release_at: u64, // block number
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReserveFlow {
+    Locked,    // locked on chain, inbound
+    Minted,    // minted here against locked funds
+    Burned,    // burned here for withdraw to chain
+    Withdrawn, // unlocked on chain, ack received
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct BridgeReserve {
+    pub locked: Uint128,
+    pub minted: Uint128,
+    pub burned: Uint128,
+    pub withdrawn: Uint128,
+}
+
+impl BridgeReserve {
+    pub fn record(&mut self, flow: ReserveFlow, amount: Uint128) -> Result<(), Box<dyn StdError>> {
+        let total = match flow {
+            ReserveFlow::Locked => &mut self.locked,
+            ReserveFlow::Minted => &mut self.minted,
+            ReserveFlow::Burned => &mut self.burned,
+            ReserveFlow::Withdrawn => &mut self.withdrawn,
+        };
+        let res = total.checked_add(amount);
+        if res.is_err() {
+            return Err(format!("reserve {:?} overflow: {:?}", flow, res.err()).into());
+        }
+        *total = res.unwrap();
+        Ok(())
+    }
+
+    // funds still locked in gateway of chain
+    pub fn get_liquidity(&self) -> Uint128 {
+        self.locked.saturating_sub(self.withdrawn)
+    }
+
+    // burned here but not yet unlocked on chain
+    pub fn get_pending_withdraw(&self) -> Uint128 {
+        self.burned.saturating_sub(self.withdrawn)
+    }
+
+    // gateway can never unlock more than ever locked in it
+    pub fn get_deficit(&self) -> Uint128 {
+        self.burned.saturating_sub(self.locked)
+    }
+}
+
+// withdraws to dst chain collected since batch got opened
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WithdrawBatch {
+    pub dst_chain_id: String,
+    pub opened_at: u64,
+    pub entries: Vec<Uint128>,
 }
 
 #[derive(Clone, Debug)]
@@ -32,6 +222,20 @@ pub struct CrossChain {
     pub tmp_idx_mp: Arc<RwLock<HashMap<(String, String), usize>>>, // string,nonce: idx proposal, as of now chain,nonce, should be mapped hash value of data -> idx
     pub contract_configs: Arc<RwLock<HashMap<String, ContractConfig>>>, // vector of chain_ids supported here
     pub requests: Arc<RwLock<HashMap<(String, String), CrossChainExecutionResult>>>, // (src_chain_id,nonce) -> CrossChainExecutionResult
+    pub assignments: Arc<RwLock<HashMap<(String, String), CrossChainAssignment>>>, // (src_chain_id,nonce) -> validator responsible to execute it on dst chain
+    pub pending_inbound: Arc<RwLock<HashMap<String, BTreeMap<Uint128, Proposal>>>>, // src_chain_id -> nonce -> proposal waiting for previous nonces
+    pub rate_limits: Arc<RwLock<HashMap<(String, TokenId), RateLimitConfig>>>, // (chain_id,token_id) -> limit of flow between digichain and chain
+    pub rate_limit_usage: Arc<RwLock<HashMap<(String, TokenId), Vec<RateLimitUsage>>>>, // (chain_id,token_id) -> usage in current window
+    pub delayed_mints: Arc<RwLock<Vec<DelayedMint>>>,
+    pub reserves: Arc<RwLock<HashMap<(String, TokenId), BridgeReserve>>>, // (chain_id,token_id) -> bridged totals
+    pub message_handlers: Arc<RwLock<HashMap<Address, MessageHandler>>>, // handler address -> handler of inbound messages
+    pub fee_configs: Arc<RwLock<HashMap<String, FeeConfig>>>, // dst chain_id -> fee charged for transfers to it
+    pub headers: Arc<RwLock<HashMap<String, BTreeMap<u64, BlockHeader>>>>, // src chain_id -> block number -> header tracked by light client
+    pub batch_configs: Arc<RwLock<HashMap<String, BatchConfig>>>, // dst chain_id -> when withdraws to it are sealed into batch
+    pub pending_batches: Arc<RwLock<HashMap<String, WithdrawBatch>>>, // dst chain_id -> batch not sealed yet
+    pub checkpoints: Arc<RwLock<BTreeMap<u64, CheckpointMsg>>>, // block number -> checkpoint signed by quorum
+    pub anchors: Arc<RwLock<Vec<AnchorRecord>>>, // checkpoints posted to anchor chain by this node
+    pub chain_assets: Arc<RwLock<HashMap<String, HashMap<Address, String>>>>, // chain_id -> stand-in address -> asset not fitting 20 bytes
 
     pub broadcasted: Arc<
         RwLock<
@@ -51,6 +255,20 @@ impl CrossChain {
             tmp_idx_mp: Arc::new(RwLock::new(tmp_idx_mp)),
             contract_configs: Arc::new(RwLock::new(HashMap::new())),
             requests: Arc::new(RwLock::new(HashMap::new())),
+            assignments: Arc::new(RwLock::new(HashMap::new())),
+            pending_inbound: Arc::new(RwLock::new(HashMap::new())),
+            rate_limits: Arc::new(RwLock::new(HashMap::new())),
+            rate_limit_usage: Arc::new(RwLock::new(HashMap::new())),
+            delayed_mints: Arc::new(RwLock::new(Vec::new())),
+            reserves: Arc::new(RwLock::new(HashMap::new())),
+            message_handlers: Arc::new(RwLock::new(HashMap::new())),
+            fee_configs: Arc::new(RwLock::new(HashMap::new())),
+            headers: Arc::new(RwLock::new(HashMap::new())),
+            batch_configs: Arc::new(RwLock::new(HashMap::new())),
+            pending_batches: Arc::new(RwLock::new(HashMap::new())),
+            checkpoints: Arc::new(RwLock::new(BTreeMap::new())),
+            anchors: Arc::new(RwLock::new(Vec::new())),
+            chain_assets: Arc::new(RwLock::new(HashMap::new())),
             broadcasted: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -81,10 +299,127 @@ impl CrossChain {
                 return Err(format!("Already Broadcasted").into());
             }
         }
+        // request might have been reassigned, only one validator can execute it on dst chain
+        if vhmp.values().any(|broadcasted| *broadcasted) {
+            return Err(format!("Already Broadcasted by other validator").into());
+        }
         vhmp.insert(validator, true);
         Ok(true)
     }
 
+    // execution of validator reverted on dst chain, it can broadcast request again
+    pub fn clear_broadcasted(
+        &mut self,
+        validator: Address,
+        src_chain_id: String,
+        src_nonce: Uint128,
+    ) {
+        let mut binding = self.broadcasted.write().unwrap();
+        if let Some(vhmp) = binding.get_mut(&(src_chain_id, src_nonce.to_string())) {
+            vhmp.remove(&validator);
+        }
+    }
+
+    // validator which relayed request to dst chain, it gets escrowed fees on ack
+    pub fn get_broadcaster(&self, src_chain_id: String, src_nonce: Uint128) -> Option<Address> {
+        let binding = self.broadcasted.read().unwrap();
+        let res = binding.get(&(src_chain_id, src_nonce.to_string()));
+        if res.is_none() {
+            return None;
+        }
+        res.unwrap()
+            .iter()
+            .find(|(_, broadcasted)| **broadcasted)
+            .map(|(validator, _)| *validator)
+    }
+
+    pub fn is_broadcasted_by_any(&self, src_chain_id: String, src_nonce: Uint128) -> bool {
+        let binding = self.broadcasted.read().unwrap();
+        let res = binding.get(&(src_chain_id, src_nonce.to_string()));
+        if res.is_none() {
+            return false;
+        }
+        res.unwrap().values().any(|broadcasted| *broadcasted)
+    }
+
+    pub fn assign_request(
+        &mut self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+        validator: Address,
+        block_number: u64,
+    ) -> CrossChainAssignment {
+        let assignment = CrossChainAssignment {
+            validator,
+            assigned_at: block_number,
+            deadline: block_number + CrossChainRequestTimeout,
+            reassignments: vec![],
+        };
+        let mut binding = self.assignments.write().unwrap();
+        binding.insert((src_chain_id, src_nonce.to_string()), assignment.clone());
+        assignment
+    }
+
+    pub fn get_assignment(
+        &self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+    ) -> Result<CrossChainAssignment, Box<dyn StdError>> {
+        let binding = self.assignments.read().unwrap();
+        let res = binding.get(&(src_chain_id, src_nonce.to_string()));
+        if res.is_none() {
+            return Err(format!("assignment not found").into());
+        }
+        Ok(res.unwrap().clone())
+    }
+
+    pub fn reassign_request(
+        &mut self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+        validator: Address,
+        block_number: u64,
+    ) -> Result<CrossChainAssignment, Box<dyn StdError>> {
+        let mut binding = self.assignments.write().unwrap();
+        let res = binding.get_mut(&(src_chain_id, src_nonce.to_string()));
+        if res.is_none() {
+            return Err(format!("assignment not found").into());
+        }
+        let assignment = res.unwrap();
+        if assignment.validator != validator {
+            assignment
+                .reassignments
+                .push((assignment.validator, block_number));
+            assignment.validator = validator;
+        }
+        assignment.assigned_at = block_number;
+        assignment.deadline = block_number + CrossChainRequestTimeout;
+        Ok(assignment.clone())
+    }
+
+    // executed on dst chain and ack received, no need to track deadline anymore
+    pub fn remove_assignment(&mut self, src_chain_id: String, src_nonce: Uint128) -> bool {
+        let mut binding = self.assignments.write().unwrap();
+        binding
+            .remove(&(src_chain_id, src_nonce.to_string()))
+            .is_some()
+    }
+
+    pub fn get_expired_assignments(
+        &self,
+        block_number: u64,
+    ) -> Vec<((String, String), CrossChainAssignment)> {
+        let binding = self.assignments.read().unwrap();
+        let mut expired = binding
+            .iter()
+            .filter(|(_, assignment)| assignment.deadline < block_number)
+            .map(|(key, assignment)| (key.clone(), assignment.clone()))
+            .collect::<Vec<((String, String), CrossChainAssignment)>>();
+        // same order on every node
+        expired.sort_by(|a, b| a.0.cmp(&b.0));
+        expired
+    }
+
     pub fn is_broadcasted(
         &self,
         validator: Address,
@@ -128,6 +463,62 @@ impl CrossChain {
         true
     }
 
+    // sorted by src chain and nonce, so explorer can paginate over it
+    pub fn get_requests(
+        &self,
+        src_chain_id: Option<String>,
+        src_nonce: Option<Uint128>,
+        dst_chain_id: Option<String>,
+    ) -> Vec<(String, Uint128, CrossChainExecutionResult)> {
+        let binding = self.requests.read().unwrap();
+        let mut requests = binding
+            .iter()
+            .filter_map(|((chain_id, nonce), request)| {
+                let nonce = Uint128::from_str(nonce);
+                if nonce.is_err() {
+                    return None;
+                }
+                let nonce = nonce.unwrap();
+                if src_chain_id.is_some() && src_chain_id.as_ref() != Some(chain_id) {
+                    return None;
+                }
+                if src_nonce.is_some() && src_nonce != Some(nonce) {
+                    return None;
+                }
+                if dst_chain_id.is_some() && dst_chain_id != request.dst_chain_id {
+                    return None;
+                }
+                Some((chain_id.clone(), nonce, request.clone()))
+            })
+            .collect::<Vec<(String, Uint128, CrossChainExecutionResult)>>();
+        requests.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+        requests
+    }
+
+    // only tracked requests move, returns false if request is unknown or already final
+    pub fn update_request_status(
+        &mut self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+        status: CrossChainRequestStatus,
+        block_number: u64,
+    ) -> bool {
+        let mut binding = self.requests.write().unwrap();
+        let res = binding.get_mut(&(src_chain_id, src_nonce.to_string()));
+        if res.is_none() {
+            return false;
+        }
+        let request = res.unwrap();
+        if request
+            .get_status()
+            .map_or(false, |status| status.is_final())
+        {
+            return false;
+        }
+        request.update_status(status, block_number);
+        true
+    }
+
     pub fn update_contract_config(
         &mut self,
         src_chain_id: String,
@@ -146,6 +537,123 @@ impl CrossChain {
         Ok(res.clone())
     }
 
+    // Ok(true) if nonce is next in sequence, Ok(false) if previous nonces are still missing
+    pub fn check_inbound_nonce(
+        &self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+    ) -> Result<bool, Box<dyn StdError>> {
+        let binding = self.contract_configs.read().unwrap();
+        let res = binding.get(&src_chain_id);
+        if res.is_none() {
+            return Err("contract config not found".into());
+        }
+        let last_processed_nonce = res.unwrap().last_proccessed_nonce;
+        if src_nonce <= last_processed_nonce {
+            return Err(format!(
+                "nonce {} already processed, last processed nonce: {}",
+                src_nonce, last_processed_nonce
+            )
+            .into());
+        }
+        if src_nonce > last_processed_nonce + Uint128::from(MaxInboundNonceGap) {
+            return Err(format!(
+                "nonce {} too far ahead, last processed nonce: {}",
+                src_nonce, last_processed_nonce
+            )
+            .into());
+        }
+        let binding = self.pending_inbound.read().unwrap();
+        if let Some(pending) = binding.get(&src_chain_id) {
+            if pending.contains_key(&src_nonce) {
+                return Err(format!("nonce {} already waiting for execution", src_nonce).into());
+            }
+        }
+        Ok(src_nonce == last_processed_nonce + Uint128::from(1u128))
+    }
+
+    pub fn buffer_inbound_request(
+        &mut self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+        proposal: Proposal,
+    ) {
+        let mut binding = self.pending_inbound.write().unwrap();
+        binding
+            .entry(src_chain_id)
+            .or_insert_with(BTreeMap::new)
+            .insert(src_nonce, proposal);
+    }
+
+    // removes buffered request which is next in sequence of any src chain
+    pub fn take_ready_inbound_request(&mut self) -> Option<Proposal> {
+        let configs = self.contract_configs.read().unwrap();
+        let mut binding = self.pending_inbound.write().unwrap();
+        let mut chain_ids = binding.keys().cloned().collect::<Vec<String>>();
+        // same order on every node
+        chain_ids.sort();
+        for chain_id in chain_ids {
+            let res = configs.get(&chain_id);
+            if res.is_none() {
+                continue;
+            }
+            let next_nonce = res.unwrap().last_proccessed_nonce + Uint128::from(1u128);
+            let pending = binding.get_mut(&chain_id).unwrap();
+            // stale ones can never be executed
+            let stale = pending
+                .range(..next_nonce)
+                .map(|(nonce, _)| nonce.clone())
+                .collect::<Vec<Uint128>>();
+            for nonce in stale {
+                pending.remove(&nonce);
+            }
+            let proposal = pending.remove(&next_nonce);
+            if pending.is_empty() {
+                binding.remove(&chain_id);
+            }
+            if proposal.is_some() {
+                return proposal;
+            }
+        }
+        None
+    }
+
+    // missing nonces before highest buffered nonce, relayer has to backfill these
+    pub fn get_inbound_nonce_gaps(&self, src_chain_id: String) -> Vec<Uint128> {
+        let configs = self.contract_configs.read().unwrap();
+        let res = configs.get(&src_chain_id);
+        if res.is_none() {
+            return vec![];
+        }
+        let mut nonce = res.unwrap().last_proccessed_nonce + Uint128::from(1u128);
+        let binding = self.pending_inbound.read().unwrap();
+        let res = binding.get(&src_chain_id);
+        if res.is_none() {
+            return vec![];
+        }
+        let pending = res.unwrap();
+        let mut gaps: Vec<Uint128> = Vec::new();
+        for buffered_nonce in pending.keys() {
+            while nonce < *buffered_nonce {
+                gaps.push(nonce);
+                nonce = nonce + Uint128::from(1u128);
+            }
+            if nonce == *buffered_nonce {
+                nonce = nonce + Uint128::from(1u128);
+            }
+        }
+        gaps
+    }
+
+    pub fn get_pending_inbound_nonces(&self, src_chain_id: String) -> Vec<Uint128> {
+        let binding = self.pending_inbound.read().unwrap();
+        let res = binding.get(&src_chain_id);
+        if res.is_none() {
+            return vec![];
+        }
+        res.unwrap().keys().cloned().collect()
+    }
+
     pub fn get_ccr_idx(
         &self,
         chain_id: String,
@@ -164,17 +672,42 @@ impl CrossChain {
         self.self_nonce
     }
 
+    // contract address of config is in format of chain
     pub fn is_contract_registered(&self, chain_id: String, contract: Address) -> bool {
+        let res = self.get_chain_adapter(chain_id.clone());
+        if res.is_err() {
+            return false;
+        }
+        let adapter = res.unwrap();
         let binding = self.contract_configs.read().unwrap();
-        if !binding.contains_key(&chain_id) {
+        let res = binding.get(&chain_id);
+        if res.is_none() {
             return false;
         }
-        if binding.get(&chain_id).unwrap().contract_address != address_to_str(contract) {
+        let res = adapter.parse_address(&res.unwrap().contract_address);
+        if res.is_err() || res.unwrap() != contract {
             return false;
         }
         true
     }
 
+    // 32 byte cw20 contracts and native denoms get stand-in address, 20 byte ones are used as is
+    pub fn register_chain_asset(&mut self, chain_id: String, asset: String) {
+        if Address::from_str(&asset).is_ok() {
+            return;
+        }
+        let asset = match bech32_decode(&asset) {
+            Ok((_, data)) if data.len() == 20usize => return,
+            Ok(_) => asset.to_lowercase(),
+            Err(_) => asset,
+        };
+        let mut binding = self.chain_assets.write().unwrap();
+        binding
+            .entry(chain_id)
+            .or_insert(HashMap::new())
+            .insert(get_asset_address(&asset), asset);
+    }
+
     pub fn get_contracts_config(
         &self,
         mut chain_ids: Vec<String>,
@@ -212,12 +745,17 @@ impl CrossChain {
         start_block: u64,
         chain_type: u8,
     ) -> Result<bool, Box<dyn StdError>> {
+        let res = new_chain_adapter(chain_type, &contract_address, HashMap::new());
+        if res.is_err() {
+            return Err(res.err().unwrap());
+        }
         let config = ContractConfig {
             contract_address: contract_address.to_lowercase(),
             start_block,
             last_proccessed_nonce: Uint128::from(0u128),
             last_processed_block: start_block,
             chain_type,
+            disabled: false,
         };
         let mut binding = self.contract_configs.write().unwrap();
         if binding.contains_key(&chain_id) {
@@ -226,6 +764,491 @@ impl CrossChain {
         binding.insert(chain_id, config);
         return Ok(true);
     }
+
+    // requests with chain which still need its current gateway, its nonces can't be reset till then
+    pub fn has_open_requests(&self, chain_id: String) -> bool {
+        if self
+            .pending_inbound
+            .read()
+            .unwrap()
+            .get(&chain_id)
+            .map_or(false, |pending| pending.len() > 0)
+        {
+            return true;
+        }
+        if self
+            .delayed_mints
+            .read()
+            .unwrap()
+            .iter()
+            .any(|delayed_mint| delayed_mint.src_chain_id == chain_id)
+        {
+            return true;
+        }
+        if self.pending_batches.read().unwrap().contains_key(&chain_id) {
+            return true;
+        }
+        let binding = self.requests.read().unwrap();
+        binding.values().any(|request| {
+            request.dst_chain_id.as_ref() == Some(&chain_id)
+                && !request
+                    .get_status()
+                    .map_or(false, |status| status.is_final())
+        })
+    }
+
+    // gateway redeployed on other address starts its nonces again, so requests keyed by its old nonces
+    // have to be closed first
+    pub fn modify_contract_config(
+        &mut self,
+        chain_id: String,
+        contract_address: String,
+        start_block: u64,
+        chain_type: u8,
+    ) -> Result<ContractConfig, Box<dyn StdError>> {
+        let res = new_chain_adapter(chain_type, &contract_address, HashMap::new());
+        if res.is_err() {
+            return Err(res.err().unwrap());
+        }
+        let has_open_requests = self.has_open_requests(chain_id.clone());
+        let mut binding = self.contract_configs.write().unwrap();
+        let res = binding.get_mut(&chain_id);
+        if res.is_none() {
+            return Err(format!("contract config not found").into());
+        }
+        let config = res.unwrap();
+        let contract_address = contract_address.to_lowercase();
+        if config.contract_address != contract_address {
+            if has_open_requests {
+                return Err(format!(
+                    "chain {} has open requests with gateway {}",
+                    chain_id, config.contract_address
+                )
+                .into());
+            }
+            config.contract_address = contract_address;
+            config.last_proccessed_nonce = Uint128::from(0u128);
+            // new contract is scanned from its start block
+            config.last_processed_block = start_block;
+            self.pending_inbound.write().unwrap().remove(&chain_id);
+        }
+        config.start_block = start_block;
+        config.chain_type = chain_type;
+        Ok(config.clone())
+    }
+
+    // chains without contract config are treated as evm chains
+    pub fn get_chain_adapter(
+        &self,
+        chain_id: String,
+    ) -> Result<Box<dyn ChainAdapter>, Box<dyn StdError>> {
+        let binding = self.contract_configs.read().unwrap();
+        let res = binding.get(&chain_id);
+        if res.is_none() {
+            return Ok(Box::new(EvmAdapter));
+        }
+        let config = res.unwrap();
+        let assets = self
+            .chain_assets
+            .read()
+            .unwrap()
+            .get(&chain_id)
+            .cloned()
+            .unwrap_or_default();
+        new_chain_adapter(config.chain_type, &config.contract_address, assets)
+    }
+
+    pub fn set_chain_disabled(
+        &mut self,
+        chain_id: String,
+        disabled: bool,
+    ) -> Result<ContractConfig, Box<dyn StdError>> {
+        let mut binding = self.contract_configs.write().unwrap();
+        let res = binding.get_mut(&chain_id);
+        if res.is_none() {
+            return Err(format!("contract config not found").into());
+        }
+        let config = res.unwrap();
+        config.disabled = disabled;
+        Ok(config.clone())
+    }
+
+    // re-added config starts nonces again, same as gateway address change
+    pub fn remove_contract_config(
+        &mut self,
+        chain_id: String,
+    ) -> Result<ContractConfig, Box<dyn StdError>> {
+        if self.has_open_requests(chain_id.clone()) {
+            return Err(format!("chain {} has open requests", chain_id).into());
+        }
+        let mut binding = self.contract_configs.write().unwrap();
+        let res = binding.remove(&chain_id);
+        if res.is_none() {
+            return Err(format!("contract config not found").into());
+        }
+        self.pending_inbound.write().unwrap().remove(&chain_id);
+        Ok(res.unwrap())
+    }
+
+    pub fn set_rate_limit(&mut self, chain_id: String, token_id: TokenId, config: RateLimitConfig) {
+        let mut binding = self.rate_limits.write().unwrap();
+        if config.window == 0u64 {
+            binding.remove(&(chain_id, token_id));
+            return;
+        }
+        binding.insert((chain_id, token_id), config);
+    }
+
+    pub fn get_rate_limit(&self, chain_id: String, token_id: TokenId) -> Option<RateLimitConfig> {
+        let binding = self.rate_limits.read().unwrap();
+        binding.get(&(chain_id, token_id)).cloned()
+    }
+
+    // errors if amount doesn't fit in chain or account cap of current window, or usage can't be summed up
+    pub fn check_rate_limit(
+        &self,
+        chain_id: String,
+        token_id: TokenId,
+        account: Address,
+        amount: Uint128,
+        block_number: u64,
+    ) -> Result<bool, Box<dyn StdError>> {
+        let res = self.get_rate_limit(chain_id.clone(), token_id.clone());
+        if res.is_none() {
+            return Ok(true);
+        }
+        let config = res.unwrap();
+        let binding = self.rate_limit_usage.read().unwrap();
+        let mut used = Uint128::zero();
+        let mut account_used = Uint128::zero();
+        if let Some(usage) = binding.get(&(chain_id.clone(), token_id.clone())) {
+            for entry in usage.iter() {
+                let res = entry.block_number.checked_add(config.window);
+                if res.is_none() {
+                    return Err(format!(
+                        "rate limit window overflow for {} on {}",
+                        token_id, chain_id
+                    )
+                    .into());
+                }
+                if res.unwrap() <= block_number {
+                    continue;
+                }
+                let res = used.checked_add(entry.amount);
+                if res.is_err() {
+                    return Err(format!("rate limit usage overflow: {:?}", res.err()).into());
+                }
+                used = res.unwrap();
+                if entry.account == account {
+                    let res = account_used.checked_add(entry.amount);
+                    if res.is_err() {
+                        return Err(format!("rate limit usage overflow: {:?}", res.err()).into());
+                    }
+                    account_used = res.unwrap();
+                }
+            }
+        }
+        let res = used.checked_add(amount);
+        if res.is_err() {
+            return Err(format!("rate limit usage overflow: {:?}", res.err()).into());
+        }
+        if config.cap != Uint128::zero() && res.unwrap() > config.cap {
+            return Err(format!(
+                "rate limit exceeded for {} on {}, used: {}, cap: {}",
+                token_id, chain_id, used, config.cap
+            )
+            .into());
+        }
+        let res = account_used.checked_add(amount);
+        if res.is_err() {
+            return Err(format!("rate limit usage overflow: {:?}", res.err()).into());
+        }
+        if config.account_cap != Uint128::zero() && res.unwrap() > config.account_cap {
+            return Err(format!(
+                "account rate limit exceeded for {} on {}, used: {}, cap: {}",
+                token_id, chain_id, account_used, config.account_cap
+            )
+            .into());
+        }
+        Ok(true)
+    }
+
+    pub fn record_rate_limit_usage(
+        &mut self,
+        chain_id: String,
+        token_id: TokenId,
+        account: Address,
+        amount: Uint128,
+        block_number: u64,
+    ) {
+        let res = self.get_rate_limit(chain_id.clone(), token_id.clone());
+        if res.is_none() {
+            return;
+        }
+        let window = res.unwrap().window;
+        let mut binding = self.rate_limit_usage.write().unwrap();
+        let usage = binding.entry((chain_id, token_id)).or_insert_with(Vec::new);
+        // out of window entries are not needed anymore
+        usage.retain(|entry| {
+            entry
+                .block_number
+                .checked_add(window)
+                .map_or(true, |end| end > block_number)
+        });
+        usage.push(RateLimitUsage {
+            account,
+            amount,
+            block_number,
+        });
+    }
+
+    pub fn queue_delayed_mint(&mut self, delayed_mint: DelayedMint) {
+        self.delayed_mints.write().unwrap().push(delayed_mint);
+    }
+
+    pub fn take_due_delayed_mints(&mut self, block_number: u64) -> Vec<DelayedMint> {
+        let mut binding = self.delayed_mints.write().unwrap();
+        let (due, pending): (Vec<DelayedMint>, Vec<DelayedMint>) = binding
+            .drain(..)
+            .partition(|delayed_mint| delayed_mint.release_at <= block_number);
+        *binding = pending;
+        due
+    }
+
+    pub fn record_reserve_flow(
+        &mut self,
+        chain_id: String,
+        token_id: TokenId,
+        flow: ReserveFlow,
+        amount: Uint128,
+    ) -> Result<(), Box<dyn StdError>> {
+        let mut binding = self.reserves.write().unwrap();
+        binding
+            .entry((chain_id, token_id))
+            .or_insert_with(BridgeReserve::default)
+            .record(flow, amount)
+    }
+
+    pub fn set_reserve(&mut self, chain_id: String, token_id: TokenId, reserve: BridgeReserve) {
+        let mut binding = self.reserves.write().unwrap();
+        binding.insert((chain_id, token_id), reserve);
+    }
+
+    pub fn get_reserve(&self, chain_id: String, token_id: TokenId) -> BridgeReserve {
+        let binding = self.reserves.read().unwrap();
+        binding
+            .get(&(chain_id, token_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_fee_config(&mut self, chain_id: String, config: FeeConfig) {
+        let mut binding = self.fee_configs.write().unwrap();
+        if config.flat.is_zero() && config.bps == 0u64 {
+            binding.remove(&chain_id);
+            return;
+        }
+        binding.insert(chain_id, config);
+    }
+
+    pub fn get_fee_config(&self, chain_id: String) -> Option<FeeConfig> {
+        let binding = self.fee_configs.read().unwrap();
+        binding.get(&chain_id).cloned()
+    }
+
+    // pending batch of chain is sealed on next check, even if batching got removed
+    pub fn set_batch_config(&mut self, chain_id: String, config: BatchConfig) {
+        let mut binding = self.batch_configs.write().unwrap();
+        if config.max_size == 0u64 {
+            binding.remove(&chain_id);
+            return;
+        }
+        binding.insert(chain_id, config);
+    }
+
+    pub fn get_batch_config(&self, chain_id: String) -> Option<BatchConfig> {
+        let binding = self.batch_configs.read().unwrap();
+        binding.get(&chain_id).cloned()
+    }
+
+    pub fn add_to_batch(&mut self, dst_chain_id: String, src_nonce: Uint128, block_number: u64) {
+        let mut binding = self.pending_batches.write().unwrap();
+        let batch = binding
+            .entry(dst_chain_id.clone())
+            .or_insert(WithdrawBatch {
+                dst_chain_id,
+                opened_at: block_number,
+                entries: vec![],
+            });
+        batch.entries.push(src_nonce);
+    }
+
+    // batches full or open for long enough, removed from pending
+    pub fn take_due_batches(&mut self, block_number: u64) -> Vec<WithdrawBatch> {
+        let configs = self.batch_configs.read().unwrap();
+        let mut binding = self.pending_batches.write().unwrap();
+        let due = binding
+            .iter()
+            .filter(|(chain_id, batch)| {
+                let res = configs.get(*chain_id);
+                if res.is_none() {
+                    return true;
+                }
+                let config = res.unwrap();
+                batch.entries.len() as u64 >= config.max_size
+                    || block_number >= batch.opened_at.saturating_add(config.max_blocks)
+            })
+            .map(|(chain_id, _)| chain_id.clone())
+            .collect::<Vec<String>>();
+        due.into_iter()
+            .filter_map(|chain_id| binding.remove(&chain_id))
+            .collect()
+    }
+
+    // batch which failed to seal goes back to pending, ahead of withdraws added since
+    pub fn restore_batch(&mut self, batch: WithdrawBatch) {
+        let mut binding = self.pending_batches.write().unwrap();
+        let pending = binding
+            .entry(batch.dst_chain_id.clone())
+            .or_insert(WithdrawBatch {
+                dst_chain_id: batch.dst_chain_id.clone(),
+                opened_at: batch.opened_at,
+                entries: vec![],
+            });
+        pending.opened_at = batch.opened_at;
+        let mut entries = batch.entries;
+        entries.append(&mut pending.entries);
+        pending.entries = entries;
+    }
+
+    // leaf of withdraw is its msg hash, same as it would be signed alone
+    pub fn get_batch_leaves(
+        &self,
+        dst_chain_id: String,
+        entries: &Vec<Uint128>,
+    ) -> Result<Vec<[u8; 32]>, Box<dyn StdError>> {
+        let mut leaves: Vec<[u8; 32]> = Vec::new();
+        for src_nonce in entries {
+            let res = self.get_request(self.self_chain_id.clone(), *src_nonce);
+            if res.is_err() {
+                return Err(format!("batched withdraw {}: {:?}", src_nonce, res.err()).into());
+            }
+            let res = res.unwrap().payload;
+            if res.is_none() {
+                return Err(format!("batched withdraw {} has no payload", src_nonce).into());
+            }
+            leaves.push(get_crosschain_withdraw_msg_hash(
+                self.self_chain_id.clone(),
+                dst_chain_id.clone(),
+                *src_nonce,
+                &res.unwrap(),
+            )?);
+        }
+        Ok(leaves)
+    }
+
+    pub fn add_checkpoint(&mut self, checkpoint: CheckpointMsg) -> Result<bool, Box<dyn StdError>> {
+        let mut binding = self.checkpoints.write().unwrap();
+        if binding.contains_key(&checkpoint.block_number) {
+            return Err(format!("checkpoint {} already present", checkpoint.block_number).into());
+        }
+        binding.insert(checkpoint.block_number, checkpoint);
+        Ok(true)
+    }
+
+    pub fn get_latest_checkpoint(&self) -> Option<CheckpointMsg> {
+        let binding = self.checkpoints.read().unwrap();
+        binding.values().last().cloned()
+    }
+
+    pub fn record_anchor(&mut self, anchor: AnchorRecord) {
+        let mut binding = self.anchors.write().unwrap();
+        binding.push(anchor);
+    }
+
+    pub fn get_last_anchor(&self) -> Option<AnchorRecord> {
+        let binding = self.anchors.read().unwrap();
+        binding.last().cloned()
+    }
+
+    // first header of chain is trusted checkpoint, later ones have to extend tracked headers
+    pub fn add_header(
+        &mut self,
+        chain_id: String,
+        header: BlockHeader,
+    ) -> Result<bool, Box<dyn StdError>> {
+        if !self
+            .contract_configs
+            .read()
+            .unwrap()
+            .contains_key(&chain_id)
+        {
+            return Err(format!("contract config not found").into());
+        }
+        let mut binding = self.headers.write().unwrap();
+        let headers = binding.entry(chain_id).or_insert_with(BTreeMap::new);
+        if let Some(tracked) = headers.get(&header.number) {
+            if tracked.hash != header.hash {
+                return Err(format!("conflicting header at {}", header.number).into());
+            }
+            return Ok(false);
+        }
+        if headers.len() > 0usize {
+            let parent = headers.get(&header.number.saturating_sub(1u64));
+            if header.number == 0u64
+                || parent.is_none()
+                || parent.unwrap().hash != header.parent_hash
+            {
+                return Err(
+                    format!("header {} doesn't extend tracked headers", header.number).into(),
+                );
+            }
+        }
+        headers.insert(header.number, header);
+        while headers.len() > MaxTrackedHeaders {
+            headers.pop_first();
+        }
+        Ok(true)
+    }
+
+    pub fn get_header(&self, chain_id: String, number: u64) -> Option<BlockHeader> {
+        let binding = self.headers.read().unwrap();
+        binding
+            .get(&chain_id)
+            .and_then(|headers| headers.get(&number).cloned())
+    }
+
+    pub fn get_latest_header(&self, chain_id: String) -> Option<BlockHeader> {
+        let binding = self.headers.read().unwrap();
+        binding
+            .get(&chain_id)
+            .and_then(|headers| headers.values().last().cloned())
+    }
+
+    pub fn register_message_handler(&mut self, handler: Address, message_handler: MessageHandler) {
+        let mut binding = self.message_handlers.write().unwrap();
+        binding.insert(handler, message_handler);
+    }
+
+    pub fn remove_message_handler(&mut self, handler: Address) {
+        let mut binding = self.message_handlers.write().unwrap();
+        binding.remove(&handler);
+    }
+
+    pub fn get_message_handler(&self, handler: Address) -> Option<MessageHandler> {
+        let binding = self.message_handlers.read().unwrap();
+        binding.get(&handler).cloned()
+    }
+
+    // unknown chains are treated as disabled
+    pub fn is_chain_disabled(&self, chain_id: String) -> bool {
+        let binding = self.contract_configs.read().unwrap();
+        let res = binding.get(&chain_id);
+        if res.is_none() {
+            return true;
+        }
+        res.unwrap().disabled
+    }
 }
 
 impl Default for CrossChain {
@@ -236,6 +1259,20 @@ impl Default for CrossChain {
             tmp_idx_mp: Default::default(),
             contract_configs: Default::default(),
             requests: Default::default(),
+            assignments: Default::default(),
+            pending_inbound: Default::default(),
+            rate_limits: Default::default(),
+            rate_limit_usage: Default::default(),
+            delayed_mints: Default::default(),
+            reserves: Default::default(),
+            message_handlers: Default::default(),
+            fee_configs: Default::default(),
+            headers: Default::default(),
+            batch_configs: Default::default(),
+            pending_batches: Default::default(),
+            checkpoints: Default::default(),
+            anchors: Default::default(),
+            chain_assets: Default::default(),
             broadcasted: Default::default(),
         }
     }
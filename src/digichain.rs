@@ -1,26 +1,36 @@
 use crate::{
     acccount::Account,
     block::DigiBlock,
-    crosschain::CrossChain,
+    checkpoint::CheckpointMsg,
+    constants::{AnchorInterval, BatchWithdrawRequest},
+    crosschain::{
+        CrossChain, CrossChainExecutionResult, CrossChainRequestStatus, CrossChainTokenStatus,
+        ReserveFlow, WithdrawBatch,
+    },
     json_rpc::JsonRpc,
     mempool::Mempool,
     proposal::{CrossChainWithdrawMsg, ExtraData, Proposal, ProposalType},
     token::DigiToken,
     transaction::{Transaction, TxType},
     types::{Address, HexString, TokenId, TxExecutionResult},
-    utils::encode_crosschain_request_type_data,
+    utils::{
+        decode_crosschain_request_type_data, encode_crosschain_request_type_data, get_merkle_root,
+    },
     validators::Validator,
 };
 use actix_web::web;
 use cosmwasm_std::Uint128;
-use ethers::types::{Signature, U256 as EthU256};
+use ethers::{
+    types::{Signature, H256, U256 as EthU256},
+    utils::{hash_message, keccak256},
+};
 use jsonrpc_http_server::jsonrpc_core::{Params, Value};
-use rand::Rng;
 use router_wasm_bindings::ethabi::{
     decode, encode, ethereum_types::U256, Address as EthRouterAddress, ParamType, Token,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
     collections::HashMap,
     error::Error,
@@ -100,35 +110,78 @@ impl DigiChain {
     pub fn get_block_number(&self) -> u64 {
         return self.blocks.read().unwrap().len() as u64;
     }
+    pub fn is_validator(&self, address: Address) -> bool {
+        let binding = self.validators.read().unwrap();
+        binding.iter().any(|val| val.acccount.address == address)
+    }
     pub fn get_no_of_tokens(&self) -> u64 {
         return self.token_list.read().unwrap().len() as u64;
     }
 
-    pub(crate) fn get_random_validator(&self) -> Address {
-        let binding = self.validators.read().unwrap();
-        let mut rng = rand::thread_rng();
-        let index = rng.gen_range(0..binding.len());
-        return binding.get(index).unwrap().acccount.address;
+    // stake weighted pick over the active set, seeded with (src_chain_id, src_nonce, latest block hash)
+    // so that every node assigns the same validator to a crosschain request
+    pub(crate) fn get_assigned_validator(
+        &self,
+        src_chain_id: String,
+        src_nonce: Uint128,
+    ) -> Option<Address> {
+        let seed = format!(
+            "{}:{}:{}",
+            src_chain_id,
+            src_nonce,
+            self.get_previous_hash()
+        );
+        self.select_validator(seed.as_bytes(), vec![])
+    }
+
+    // excluded validators are skipped unless no one else is left,
+    // none if validator set is empty or has no stake
+    pub(crate) fn select_validator(&self, seed: &[u8], exclude: Vec<Address>) -> Option<Address> {
+        let validators = self.validators.read().unwrap();
+        let mut binding = validators
+            .iter()
+            .filter(|val| !exclude.contains(&val.acccount.address))
+            .collect::<Vec<&Validator>>();
+        if binding.len() == 0usize {
+            binding = validators.iter().collect::<Vec<&Validator>>();
+        }
+        if binding.len() == 0usize {
+            return None;
+        }
+        let hash = Sha256::digest(seed);
+        let mut rnd_bytes = [0u8; 16];
+        rnd_bytes.copy_from_slice(&hash[..16]);
+        let rnd = u128::from_be_bytes(rnd_bytes);
+
+        let total_stake = binding
+            .iter()
+            .fold(0u128, |acc, val| acc.saturating_add(val.staked.u128()));
+        if total_stake == 0u128 {
+            return None;
+        }
+        let mut point = rnd % total_stake;
+        for val in binding.iter() {
+            if point < val.staked.u128() {
+                return Some(val.acccount.address);
+            }
+            point = point - val.staked.u128();
+        }
+        binding.last().map(|val| val.acccount.address)
     }
 
     // it adds validator data into packet
     pub(crate) fn get_cmp_ccr_data(
         &self,
-        crosschain: &mut std::sync::RwLockWriteGuard<'_, CrossChain>,
         request_type: u8,
         src_chain_id: String,
         dst_chain_id: String,
-        mut src_nonce: Uint128,
-        mut dst_nonce: Uint128,
+        src_nonce: Uint128,
+        dst_nonce: Uint128,
     ) -> HexString {
-        if dst_chain_id != self.chain_id {
-            // it means request coming from other chain
-            src_nonce = crosschain.increase_nonce(); // increase nonce and return latest nonce
-        }
-        let binding = self.validators.read().unwrap();
-        let mut rng = rand::thread_rng();
-        let index = rng.gen_range(0..binding.len());
-        let validator = binding.get(index).unwrap().acccount.address;
+        // no validator to assign yet, zero address leaves request to reassignment once there is one
+        let validator = self
+            .get_assigned_validator(src_chain_id.clone(), src_nonce)
+            .unwrap_or_default();
         // convert to token to pack
         return encode_crosschain_request_type_data(
             request_type,
@@ -140,6 +193,417 @@ impl DigiChain {
         );
     }
 
+    // withdraws not acked before deadline are moved to other validator, part of block execution so it
+    // only depends on assignments recorded on chain. broadcasted map is local to node, withdraw still in
+    // flight from previous validator is rejected by gateway once other one executed same src nonce
+    pub(crate) fn reassign_expired_crosschain_requests(&mut self, block_number: u64) {
+        let expired = self
+            .crosschain
+            .read()
+            .unwrap()
+            .get_expired_assignments(block_number);
+        for ((src_chain_id, src_nonce), assignment) in expired {
+            let src_nonce = Uint128::from_str(&src_nonce).unwrap();
+            let mut exclude: Vec<Address> = assignment
+                .reassignments
+                .iter()
+                .map(|(validator, _)| validator.clone())
+                .collect();
+            exclude.push(assignment.validator);
+            let seed = format!(
+                "{}:{}:{}:{}",
+                src_chain_id,
+                src_nonce,
+                self.get_previous_hash(),
+                assignment.reassignments.len()
+            );
+            let validator = self
+                .select_validator(seed.as_bytes(), exclude)
+                .unwrap_or(assignment.validator);
+            if validator == assignment.validator {
+                // single validator or none with stake, nothing to reassign to, just extend deadline
+                let _ = self.crosschain.write().unwrap().reassign_request(
+                    src_chain_id,
+                    src_nonce,
+                    validator,
+                    block_number,
+                );
+                continue;
+            }
+            self.mempool.write().unwrap().move_crosschain_request(
+                assignment.validator,
+                validator,
+                src_chain_id.clone(),
+                src_nonce,
+            );
+            let mut crosschain = self.crosschain.write().unwrap();
+            let _ = crosschain.reassign_request(
+                src_chain_id.clone(),
+                src_nonce,
+                validator,
+                block_number,
+            );
+            crosschain.update_request_status(
+                src_chain_id.clone(),
+                src_nonce,
+                CrossChainRequestStatus::AssignedToValidator,
+                block_number,
+            );
+            drop(crosschain);
+            println!(
+                "CrossChain Request Reassigned || SrcChainId: {}, SrcNonce: {}, From: {:?}, To: {:?}",
+                src_chain_id, src_nonce, assignment.validator, validator
+            );
+        }
+    }
+
+    // merkle root over token balances, account nonces and crosschain assignments,
+    // leaves are sorted so every validator gets same root
+    pub fn get_state_root(&self) -> [u8; 32] {
+        let mut leaves: Vec<[u8; 32]> = Vec::new();
+        for token in self.token_list.read().unwrap().values() {
+            for (address, balance) in token.balance_mp.iter() {
+                leaves.push(keccak256(encode(&vec![
+                    Token::String(token.id.clone()),
+                    Token::Address(EthRouterAddress::from_slice(address.as_bytes())),
+                    Token::Uint(U256::from(balance.u128())),
+                ])));
+            }
+        }
+        for account in self.accounts.read().unwrap().values() {
+            let account = account.read().unwrap();
+            leaves.push(keccak256(encode(&vec![
+                Token::Address(EthRouterAddress::from_slice(account.address.as_bytes())),
+                Token::Uint(U256::from(account.tx_nonce.u128())),
+                Token::Uint(U256::from(account.proposal_nonce.u128())),
+            ])));
+        }
+        let crosschain = self.crosschain.read().unwrap();
+        for ((src_chain_id, src_nonce), assignment) in crosschain.assignments.read().unwrap().iter()
+        {
+            leaves.push(keccak256(encode(&vec![
+                Token::String(src_chain_id.clone()),
+                Token::String(src_nonce.clone()),
+                Token::Address(EthRouterAddress::from_slice(
+                    assignment.validator.as_bytes(),
+                )),
+                Token::Uint(U256::from(assignment.deadline)),
+            ])));
+        }
+        drop(crosschain);
+        leaves.sort();
+        get_merkle_root(&leaves)
+    }
+
+    // proposals created by this node are signed by its validator, others check it before executing them
+    pub(crate) fn sign_proposal(&self, proposal: &mut Proposal) {
+        let res = proposal.sign(&self.validator.read().unwrap().wallet);
+        if res.is_err() {
+            println!(
+                "Signing Proposal Failed || TxHash: {}, Err: {:?}",
+                proposal.hash,
+                res.err()
+            );
+        }
+    }
+
+    // checkpoint is proposed by validator of this node, others sign it while attesting
+    pub(crate) fn propose_checkpoint(&mut self, block: &DigiBlock, timestamp: u64) {
+        let checkpoint = self.get_checkpoint(block);
+        if checkpoint.is_err() {
+            println!(
+                "Checkpoint Failed || BlockNumber: {}, Error: {:?}",
+                block.index,
+                checkpoint.err()
+            );
+            return;
+        }
+        let validator = self.validator.read().unwrap().acccount.address;
+        let proposal_nonce = self
+            .get_account(validator)
+            .map_or(Uint128::zero(), |account| account.proposal_nonce);
+        let mut proposal = Proposal::new(
+            self.chain_id.clone(),
+            ProposalType::AnchorCheckpoint,
+            validator,
+            timestamp,
+            HexString::from_vec(vec![]),
+            proposal_nonce,
+            block.index,
+            Some(ExtraData::Checkpoint(checkpoint.unwrap())),
+        );
+        self.sign_proposal(&mut proposal);
+        let _ = self
+            .mempool
+            .write()
+            .unwrap()
+            .add_proposal(ProposalType::AnchorCheckpoint, proposal);
+    }
+
+    pub(crate) fn get_checkpoint(
+        &self,
+        block: &DigiBlock,
+    ) -> Result<CheckpointMsg, Box<dyn Error>> {
+        let block_hash = H256::from_str(&block.hash);
+        if block_hash.is_err() {
+            return Err(format!("block hash: {:?}", block_hash.err()).into());
+        }
+        let state_root = H256::from_str(&block.state_root);
+        if state_root.is_err() {
+            return Err(format!("state root: {:?}", state_root.err()).into());
+        }
+        Ok(CheckpointMsg::new(
+            self.chain_id.clone(),
+            block.index,
+            block_hash.unwrap(),
+            state_root.unwrap(),
+        ))
+    }
+
+    // due batches are proposed as one withdraw msg carrying merkle root of their withdraws
+    pub(crate) fn seal_withdraw_batches(&mut self, block_number: u64, timestamp: u64) {
+        let due = self
+            .crosschain
+            .write()
+            .unwrap()
+            .take_due_batches(block_number);
+        for batch in due {
+            let res = self.seal_withdraw_batch(&batch, block_number, timestamp);
+            if res.is_err() {
+                println!(
+                    "Withdraw Batch Failed || DstChainId: {}, Entries: {:?}, Error: {:?}",
+                    batch.dst_chain_id,
+                    batch.entries,
+                    res.err()
+                );
+                // withdraws stay pending, sealed again in next block
+                self.crosschain.write().unwrap().restore_batch(batch);
+                continue;
+            }
+            println!(
+                "Withdraw Batch Sealed || DstChainId: {}, BatchNonce: {}, Entries: {}",
+                batch.dst_chain_id,
+                res.unwrap(),
+                batch.entries.len()
+            );
+        }
+    }
+
+    fn seal_withdraw_batch(
+        &mut self,
+        batch: &WithdrawBatch,
+        block_number: u64,
+        timestamp: u64,
+    ) -> Result<Uint128, Box<dyn Error>> {
+        let crosschain = self.crosschain.read().unwrap();
+        let adapter = crosschain.get_chain_adapter(batch.dst_chain_id.clone())?;
+        let leaves = crosschain.get_batch_leaves(batch.dst_chain_id.clone(), &batch.entries)?;
+        let root = H256::from(get_merkle_root(&leaves));
+        let payload = adapter.encode_batch_payload(root, &batch.entries)?;
+        let mut entries: Vec<(Uint128, HexString)> = Vec::new();
+        for src_nonce in batch.entries.iter() {
+            let request = crosschain.get_request(self.chain_id.clone(), *src_nonce)?;
+            entries.push((*src_nonce, request.payload.unwrap_or_default()));
+        }
+        drop(crosschain);
+
+        let batch_nonce = self.crosschain.write().unwrap().increase_nonce();
+        let udata = self.get_cmp_ccr_data(
+            BatchWithdrawRequest,
+            self.chain_id.clone(),
+            batch.dst_chain_id.clone(),
+            batch_nonce,
+            Uint128::from(0u128),
+        );
+        let mut withdraw_msg = CrossChainWithdrawMsg::new(
+            batch.dst_chain_id.clone(),
+            self.chain_id.clone(),
+            batch_nonce,
+            payload.clone(),
+            self.get_validator_set_id(),
+            adapter.chain_type(),
+        );
+        withdraw_msg.entries = entries;
+        // proposed by validator of this node
+        let validator = self.validator.read().unwrap().acccount.address;
+        let proposal_nonce = self
+            .get_account(validator)
+            .map_or(Uint128::zero(), |account| account.proposal_nonce);
+        let mut proposal = Proposal::new(
+            self.chain_id.clone(),
+            ProposalType::CrossChainRequest(udata.clone()),
+            validator,
+            timestamp,
+            HexString::from_vec(vec![]),
+            proposal_nonce,
+            block_number,
+            Some(ExtraData::WithdrawData(withdraw_msg)),
+        );
+
+        let mut crosschain = self.crosschain.write().unwrap();
+        let mut request = CrossChainExecutionResult::new(proposal.hash.clone());
+        request.dst_chain_id = Some(batch.dst_chain_id.clone());
+        request.payload = Some(payload);
+        request.batch_entries = batch.entries.clone();
+        request.update_status(CrossChainRequestStatus::Initiated, block_number);
+        crosschain.add_request(self.chain_id.clone(), batch_nonce, request);
+        for src_nonce in batch.entries.iter() {
+            let mut request = crosschain.get_request(self.chain_id.clone(), *src_nonce)?;
+            request.batch_nonce = Some(batch_nonce);
+            request.update_status(CrossChainRequestStatus::Batched, block_number);
+            crosschain.add_request(self.chain_id.clone(), *src_nonce, request);
+        }
+        drop(crosschain);
+        self.sign_proposal(&mut proposal);
+        let _ = self
+            .mempool
+            .write()
+            .unwrap()
+            .add_proposal(ProposalType::CrossChainRequest(udata), proposal);
+        Ok(batch_nonce)
+    }
+
+    // inbound mints held back by rate limit, window has passed now
+    pub(crate) fn release_delayed_mints(&mut self, block_number: u64) {
+        let due = self
+            .crosschain
+            .write()
+            .unwrap()
+            .take_due_delayed_mints(block_number);
+        for mut delayed_mint in due {
+            // usage recorded since it got queued might still fill the window
+            let window = {
+                let crosschain = self.crosschain.read().unwrap();
+                let res = crosschain.check_rate_limit(
+                    delayed_mint.src_chain_id.clone(),
+                    delayed_mint.token_id.clone(),
+                    delayed_mint.recipient,
+                    delayed_mint.amount,
+                    block_number,
+                );
+                if res.is_ok() {
+                    None
+                } else {
+                    Some(
+                        crosschain
+                            .get_rate_limit(
+                                delayed_mint.src_chain_id.clone(),
+                                delayed_mint.token_id.clone(),
+                            )
+                            .map_or(0u64, |config| config.window),
+                    )
+                }
+            };
+            if let Some(window) = window {
+                delayed_mint.release_at = block_number.saturating_add(window);
+                println!(
+                    "Delayed Mint Held Again || SrcChainId: {}, SrcNonce: {}, ReleaseAt: {}",
+                    delayed_mint.src_chain_id, delayed_mint.src_nonce, delayed_mint.release_at
+                );
+                self.crosschain
+                    .write()
+                    .unwrap()
+                    .queue_delayed_mint(delayed_mint);
+                continue;
+            }
+            let mut token_list = self.token_list.write().unwrap();
+            let res = token_list.get_mut(&delayed_mint.token_id);
+            if res.is_none() {
+                println!(
+                    "Delayed Mint Failed || SrcChainId: {}, SrcNonce: {}, TokenId: {} not found",
+                    delayed_mint.src_chain_id, delayed_mint.src_nonce, delayed_mint.token_id
+                );
+                continue;
+            }
+            res.unwrap()
+                .mint(delayed_mint.recipient, delayed_mint.amount);
+            drop(token_list);
+
+            let mut crosschain = self.crosschain.write().unwrap();
+            crosschain.record_rate_limit_usage(
+                delayed_mint.src_chain_id.clone(),
+                delayed_mint.token_id.clone(),
+                delayed_mint.recipient,
+                delayed_mint.amount,
+                block_number,
+            );
+            let res = crosschain.record_reserve_flow(
+                delayed_mint.src_chain_id.clone(),
+                delayed_mint.token_id.clone(),
+                ReserveFlow::Minted,
+                delayed_mint.amount,
+            );
+            if res.is_err() {
+                println!(
+                    "Reserve Update Failed || SrcChainId: {}, SrcNonce: {}, Error: {:?}",
+                    delayed_mint.src_chain_id,
+                    delayed_mint.src_nonce,
+                    res.err()
+                );
+            }
+            let res =
+                crosschain.get_request(delayed_mint.src_chain_id.clone(), delayed_mint.src_nonce);
+            if res.is_ok() {
+                let mut request = res.unwrap();
+                if let Some(token_result) = request.tokens.get_mut(delayed_mint.idx) {
+                    token_result.status = CrossChainTokenStatus::Minted;
+                }
+                crosschain.add_request(
+                    delayed_mint.src_chain_id.clone(),
+                    delayed_mint.src_nonce,
+                    request,
+                );
+            }
+            println!(
+                "Delayed Mint Released || SrcChainId: {}, SrcNonce: {}, TokenId: {}, Amount: {}",
+                delayed_mint.src_chain_id,
+                delayed_mint.src_nonce,
+                delayed_mint.token_id,
+                delayed_mint.amount
+            );
+        }
+    }
+
+    // identifies the validator set which signed a withdraw, gateway keeps the same id per set
+    pub fn get_validator_set_id(&self) -> String {
+        let binding = self.validators.read().unwrap();
+        let mut data: Vec<u8> = Vec::new();
+        for val in binding.iter() {
+            data.extend_from_slice(val.acccount.address.as_bytes());
+        }
+        format!("0x{}", hex::encode(keccak256(data)))
+    }
+
+    // bls public keys of signers, in same order, to verify aggregated withdraw signature,
+    // key is only used once its proof of possession is valid
+    pub fn get_bls_public_keys(
+        &self,
+        signers: &Vec<Address>,
+    ) -> Result<Vec<HexString>, Box<dyn Error>> {
+        let binding = self.validators.read().unwrap();
+        let mut public_keys: Vec<HexString> = Vec::new();
+        for signer in signers {
+            let res = binding.iter().find(|val| val.acccount.address == *signer);
+            if res.is_none() {
+                return Err(format!("{:?} is not a validator", signer).into());
+            }
+            let val = res.unwrap();
+            if val.bls_public_key.is_none() {
+                return Err(format!("{:?} bls key not registered", signer).into());
+            }
+            let public_key = val.bls_public_key.clone().unwrap();
+            #[cfg(feature = "bls")]
+            if val.bls_pop.is_none()
+                || !crate::bls::verify_possession(&public_key, val.bls_pop.as_ref().unwrap())
+                    .unwrap_or(false)
+            {
+                return Err(format!("{:?} bls proof of possession invalid", signer).into());
+            }
+            public_keys.push(public_key);
+        }
+        Ok(public_keys)
+    }
+
     pub(crate) fn add_validator(&mut self, validator: Validator) -> bool {
         let mut binding = self.validators.write().unwrap();
         binding.push(validator);
@@ -180,43 +644,134 @@ impl DigiChain {
         proposals: Vec<Proposal>,
     ) -> Vec<Proposal> {
         let mut executed_proposals: Vec<Proposal> = Vec::new();
-        for mut proposal in proposals {
-            let original_chain_state = self.clone();
-            // proposal
-            let res = proposal.execute(block_number, timestamp, self);
-            if res.is_err() {
-                // revert chain state
-                *self = original_chain_state;
-                proposal.result = TxExecutionResult::Error(format!("{:?}", res.err()));
-            } else {
-                proposal.result = TxExecutionResult::Result(format!("{:?}", res.unwrap()));
-            }
-            proposal.timestamp = timestamp;
-            proposal.block_number = block_number;
+        for proposal in proposals {
+            let proposal = self.execute_proposal(block_number, timestamp, proposal);
 
             //increase proposal nonce of from account
-            let mut binding = self.accounts.write().unwrap();
-            let mut account = binding.get(&proposal.proposed_by);
-            if account.is_none() {
-                binding.insert(
-                    proposal.proposed_by,
-                    Arc::new(RwLock::new(Account::new(proposal.proposed_by))),
-                );
-                account = binding.get(&proposal.proposed_by);
+            {
+                let mut binding = self.accounts.write().unwrap();
+                let mut account = binding.get(&proposal.proposed_by);
+                if account.is_none() {
+                    binding.insert(
+                        proposal.proposed_by,
+                        Arc::new(RwLock::new(Account::new(proposal.proposed_by))),
+                    );
+                    account = binding.get(&proposal.proposed_by);
+                }
+                let account = account.unwrap();
+                account.write().unwrap().increase_proposal_nonce();
             }
-            let account = account.unwrap();
-            account.write().unwrap().increase_proposal_nonce();
-
-            // index it
-            self.index_proposals
-                .write()
-                .unwrap()
-                .insert(proposal.hash.clone(), proposal.block_number as usize);
             executed_proposals.push(proposal);
+
+            // inbound requests buffered for missing nonce, might be in sequence now
+            while let Some(mut proposal) = self.take_ready_inbound_request() {
+                proposal.hash = proposal.get_deferred_execution_hash(block_number);
+                let proposal = self.execute_proposal(block_number, timestamp, proposal);
+                executed_proposals.push(proposal);
+            }
         }
         executed_proposals
     }
 
+    fn execute_proposal(
+        &mut self,
+        block_number: u64,
+        timestamp: u64,
+        mut proposal: Proposal,
+    ) -> Proposal {
+        let original_chain_state = self.clone();
+        // proposal
+        let res = proposal.execute(block_number, timestamp, self);
+        if res.is_err() {
+            // revert chain state
+            *self = original_chain_state;
+            let error = format!("{:?}", res.err());
+            proposal.result = TxExecutionResult::Error(error.clone());
+            if let ProposalType::CrossChainRequest(data) = &proposal.proposal_type {
+                let info = decode_crosschain_request_type_data(data);
+                if info.is_ok() {
+                    let info = info.unwrap();
+                    let mut crosschain = self.crosschain.write().unwrap();
+                    // inbound request failing on first execution isn't recorded yet
+                    if crosschain
+                        .get_request(info.src_chain_id.clone(), info.src_nonce)
+                        .is_err()
+                    {
+                        let mut request = CrossChainExecutionResult::new(String::new());
+                        request.dst_tx_hash = Some(proposal.hash.clone());
+                        request.dst_chain_id = Some(info.dst_chain_id.clone());
+                        request.error = Some(error);
+                        crosschain.add_request(info.src_chain_id.clone(), info.src_nonce, request);
+                    }
+                    // failed withdraw is never executed on dst chain, escrowed fees go back
+                    if info.src_chain_id == self.chain_id {
+                        self.refund_withdraw_fees(&mut crosschain, info.src_nonce);
+                    }
+                    crosschain.update_request_status(
+                        info.src_chain_id,
+                        info.src_nonce,
+                        CrossChainRequestStatus::Failed,
+                        block_number,
+                    );
+                }
+            }
+        } else {
+            proposal.result = TxExecutionResult::Result(format!("{:?}", res.unwrap()));
+        }
+        proposal.timestamp = timestamp;
+        proposal.block_number = block_number;
+
+        // index it
+        self.index_proposals
+            .write()
+            .unwrap()
+            .insert(proposal.hash.clone(), proposal.block_number as usize);
+        proposal
+    }
+
+    // mints unpaid fees of withdraw, or of every withdraw in batch, back to their senders
+    fn refund_withdraw_fees(&self, crosschain: &mut CrossChain, src_nonce: Uint128) {
+        let res = crosschain.get_request(self.chain_id.clone(), src_nonce);
+        if res.is_err() {
+            return;
+        }
+        let mut src_nonces = res.unwrap().batch_entries;
+        src_nonces.push(src_nonce);
+        let mut token_list = self.token_list.write().unwrap();
+        for src_nonce in src_nonces {
+            let res = crosschain.get_request(self.chain_id.clone(), src_nonce);
+            if res.is_err() {
+                continue;
+            }
+            let mut request = res.unwrap();
+            if request.fees.is_empty() {
+                continue;
+            }
+            for fee in request.fees.iter_mut() {
+                if fee.paid_to.is_some() || fee.refunded {
+                    continue;
+                }
+                if let Some(token) = token_list.get_mut(&fee.token_id) {
+                    token.mint(fee.paid_by, fee.amount);
+                    fee.refunded = true;
+                }
+            }
+            println!(
+                "Withdraw Fees Refunded || SrcChainId: {}, SrcNonce: {}",
+                self.chain_id, src_nonce
+            );
+            crosschain.add_request(self.chain_id.clone(), src_nonce, request);
+        }
+    }
+
+    // lock is released before returning, so proposal can be executed by caller
+    fn take_ready_inbound_request(&self) -> Option<Proposal> {
+        self.crosschain
+            .write()
+            .unwrap()
+            .take_ready_inbound_request()
+    }
+
     pub(crate) fn execute_txs(
         &mut self,
         block_number: u64,
@@ -225,6 +780,17 @@ impl DigiChain {
     ) -> Vec<Transaction> {
         let mut executed_txs: Vec<Transaction> = Vec::new();
         for mut tx in txs {
+            // txs of account are executed in increasing order of nonce, without gaps
+            let account_nonce = self
+                .get_account(tx.from)
+                .map_or(Uint128::zero(), |account| account.tx_nonce);
+            if tx.nonce != account_nonce {
+                println!(
+                    "Transaction Skipped || Hash: {:?}, Nonce: {}, AccountNonce: {}",
+                    tx.hash, tx.nonce, account_nonce
+                );
+                continue;
+            }
             let original_chain_state = self.clone();
             // proposal
             let res = tx.execute(block_number, timestamp, self);
@@ -284,10 +850,13 @@ impl DigiChain {
                 // fetch proposals which have been validated
             }
             // execute all tx and proposals
-            // TODO: tx will be executed in any other, but it should executed in increasing order of nonce of from account
             //TODO: update block_number of txs and proposal with block_number
             txs = self.execute_txs(block_number, timestamp, txs);
             proposals = self.execute_proposals(block_number, timestamp, proposals);
+            // part of block execution, so state root covers released mints, reassignments and sealed batches
+            self.release_delayed_mints(block_number);
+            self.reassign_expired_crosschain_requests(block_number);
+            self.seal_withdraw_batches(block_number, timestamp);
             let block = DigiBlock::create_block(
                 self.validator.read().unwrap().clone(),
                 timestamp,
@@ -295,9 +864,16 @@ impl DigiChain {
                 self.get_previous_hash(),
                 txs,
                 proposals, //TODO: implement execution of proposols
+                hex::encode(self.get_state_root()),
             );
             self.add_block(block.clone()).await;
-            self.mempool.write().unwrap().drop_tx_and_proposals(block);
+            self.mempool
+                .write()
+                .unwrap()
+                .drop_tx_and_proposals(block.clone());
+            if block_number % AnchorInterval == 0u64 {
+                self.propose_checkpoint(&block, timestamp);
+            }
             // block after every 3sec
             tokio::time::sleep(std::time::Duration::from_secs(3)).await;
         }
@@ -318,16 +894,18 @@ impl DigiChain {
                 let res = kv.1.get_mut(idx);
                 let proposal = res.unwrap();
                 if !proposal.is_signed(validator.acccount.address) {
-                    //TODO: sign tx
+                    let res = validator
+                        .wallet
+                        .sign_hash(hash_message(proposal.hash.as_bytes()));
+                    if res.is_err() {
+                        println!("Signing Proposal Failed || TxHash: {}", proposal.hash);
+                        idx = idx + 1usize;
+                        continue;
+                    }
                     println!("Signed Proposal || TxHash: {}", proposal.hash);
-                    proposal.validtors_signature.push((
-                        validator.acccount.address,
-                        Signature {
-                            r: EthU256::default(),
-                            s: EthU256::default(),
-                            v: 123u64,
-                        },
-                    ));
+                    proposal
+                        .validtors_signature
+                        .push((validator.acccount.address, res.unwrap()));
 
                     let mut variant = proposal.proposal_type.to_string();
                     if let Some(s) = variant.find('(') {
@@ -339,34 +917,111 @@ impl DigiChain {
                             CrossChainWithdrawMsg,
                             Box<dyn std::error::Error>,
                         > = match &proposal.extra_data {
-                            Some(ExtraData::WithdrawData(data)) => Ok(CrossChainWithdrawMsg {
-                                dst_chain_id: data.dst_chain_id.clone(),
-                                src_chain_id: data.src_chain_id.clone(),
-                                src_nonce: data.src_nonce,
-                                payload: data.payload.clone(),
-                                sigs: data.sigs.clone(),
-                            }),
+                            Some(ExtraData::WithdrawData(data)) => Ok(data.clone()),
                             Some(_) => Err("unknown extra data".into()),
                             None => Err("missing extra data".into()),
                         };
                         if extra_data_res.is_err() {
+                            idx = idx + 1usize;
                             continue;
                         }
                         let mut withdraw_data = extra_data_res.unwrap();
-                        withdraw_data.sigs.push(Signature {
-                            r: EthU256::default(),
-                            s: EthU256::default(),
-                            v: 123u64,
-                        });
+                        let res = self
+                            .crosschain
+                            .read()
+                            .unwrap()
+                            .get_chain_adapter(withdraw_data.dst_chain_id.clone())
+                            .and_then(|adapter| withdraw_data.attest(&validator, adapter.as_ref()));
+                        if res.is_err() {
+                            println!(
+                                "Signing Withdraw Failed || TxHash: {}, Err: {:?}",
+                                proposal.hash,
+                                res.err()
+                            );
+                            idx = idx + 1usize;
+                            continue;
+                        }
                         proposal.extra_data = Some(ExtraData::WithdrawData(withdraw_data));
                     }
+                    // checkpoint is signed only if it matches block of this node
+                    if let Some(ExtraData::Checkpoint(checkpoint)) = &proposal.extra_data {
+                        let mut checkpoint = checkpoint.clone();
+                        let res = self
+                            .get_block(checkpoint.block_number)
+                            .and_then(|block| self.get_checkpoint(&block));
+                        if res.is_err()
+                            || res.as_ref().unwrap().block_hash != checkpoint.block_hash
+                            || res.as_ref().unwrap().state_root != checkpoint.state_root
+                        {
+                            println!(
+                                "Checkpoint Mismatch || TxHash: {}, BlockNumber: {}",
+                                proposal.hash, checkpoint.block_number
+                            );
+                            idx = idx + 1usize;
+                            continue;
+                        }
+                        let res = checkpoint.sign(&validator.wallet);
+                        if res.is_err() {
+                            println!(
+                                "Signing Checkpoint Failed || TxHash: {}, Err: {:?}",
+                                proposal.hash,
+                                res.err()
+                            );
+                            idx = idx + 1usize;
+                            continue;
+                        }
+                        proposal.extra_data = Some(ExtraData::Checkpoint(checkpoint));
+                    }
                 }
+                #[cfg(feature = "bls")]
+                self.aggregate_withdraw_signatures(proposal);
                 idx = idx + 1usize;
             }
             proposals_binding.insert(kv.0, kv.1);
         }
     }
 
+    // aggregated while attesting once proposal has majority, so block execution doesn't pay for it,
+    // withdraw still carries ecdsa sigs if aggregation fails
+    #[cfg(feature = "bls")]
+    fn aggregate_withdraw_signatures(&self, proposal: &mut Proposal) {
+        let validators = self.validators.read().unwrap().len();
+        if proposal.validtors_signature.len() * 100 < 70 * validators {
+            return;
+        }
+        let withdraw_data = match &mut proposal.extra_data {
+            Some(ExtraData::WithdrawData(data)) => data,
+            _ => return,
+        };
+        if withdraw_data.bls_sigs.len() == 0usize
+            || withdraw_data.aggregated_signers.len() == withdraw_data.bls_sigs.len()
+        {
+            return;
+        }
+        let mut aggregated = withdraw_data.clone();
+        let res = aggregated.aggregate_bls_signatures();
+        if res.is_err() {
+            println!(
+                "Aggregating Withdraw Failed || TxHash: {}, Err: {:?}",
+                proposal.hash,
+                res.err()
+            );
+            return;
+        }
+        let res = self
+            .get_bls_public_keys(&aggregated.aggregated_signers)
+            .and_then(|public_keys| aggregated.verify_aggregated_signature(&public_keys));
+        if res.is_err() || !res.as_ref().unwrap() {
+            println!(
+                "Aggregating Withdraw Failed || TxHash: {}, Err: {:?}",
+                proposal.hash,
+                res.err()
+            );
+            return;
+        }
+        *withdraw_data = aggregated;
+    }
+
     ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
     /////////////////////////////////////////////////Digi Token////////////////////////////////////////////////////////////////////////////
     ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -386,6 +1041,17 @@ impl DigiChain {
     ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
     /////////////////////////////////////////////////Digi Token////////////////////////////////////////////////////////////////////////////
     ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+    // nonce next tx of account has to carry, after its txs waiting in mempool
+    pub fn get_pending_tx_nonce(&self, address: Address) -> Uint128 {
+        let account_nonce = self
+            .get_account(address)
+            .map_or(Uint128::zero(), |account| account.tx_nonce);
+        self.mempool
+            .read()
+            .unwrap()
+            .get_pending_nonce(address, account_nonce)
+    }
+
     pub fn get_account(&self, address: Address) -> Result<Account, Box<dyn std::error::Error>> {
         let binding = self.accounts.read().unwrap();
         let res = binding.get(&address);
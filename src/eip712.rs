@@ -0,0 +1,439 @@
+use crate::{
+    constants::{Eip712DomainName, Eip712DomainVersion},
+    proposal::{get_kyc_data, ProposalType, RawProposal},
+    transaction::{TxSigningPayload, TxType},
+    types::HexString,
+    utils::{
+        get_crosschain_message_payload_params, get_crosschain_transfer_payload_params,
+        get_transfer_payload_params,
+    },
+};
+use ethers::{
+    core::types::transaction::eip712::{Eip712, TypedData},
+    types::H256,
+    utils::keccak256,
+};
+use serde_json::{json, Value};
+use std::error::Error as StdError;
+
+// typed data for signing with eth_signTypedData_v4, digichain chain id is bound through domain salt
+// as it need not be an evm chain id
+fn get_domain() -> Value {
+    json!({
+        "name": Eip712DomainName,
+        "version": Eip712DomainVersion,
+    })
+}
+
+// ethers reads salt as byte array, not as hex string wallets use, so it is set after parsing
+fn parse_typed_data(typed_data: Value, chain_id: &String) -> Result<TypedData, Box<dyn StdError>> {
+    let res: Result<TypedData, serde_json::Error> = serde_json::from_value(typed_data);
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    let mut typed_data = res.unwrap();
+    typed_data.domain.salt = Some(keccak256(chain_id.as_bytes()));
+    Ok(typed_data)
+}
+
+// typed data as passed to eth_signTypedData_v4, with salt as hex string
+pub fn get_wallet_typed_data(typed_data: &TypedData) -> Value {
+    let mut value = json!(typed_data);
+    if let Some(salt) = typed_data.domain.salt {
+        value["domain"]["salt"] = json!(format!("{:?}", H256::from(salt)));
+    }
+    value
+}
+
+fn get_domain_type() -> Value {
+    json!([
+        {"name": "name", "type": "string"},
+        {"name": "version", "type": "string"},
+        {"name": "salt", "type": "bytes32"},
+    ])
+}
+
+fn to_hex_bytes(data: &HexString) -> Result<String, Box<dyn StdError>> {
+    let res = data.to_vec();
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    Ok(format!("0x{}", hex::encode(res.unwrap())))
+}
+
+// decoded fields are shown to user, hash binds them to exact bytes of tx data
+fn get_data_hash(data: &HexString) -> Result<String, Box<dyn StdError>> {
+    let res = data.to_vec();
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    Ok(format!("{:?}", H256::from(keccak256(res.unwrap()))))
+}
+
+// variant name without its data, e.g. CrossChainRequest
+fn get_variant_name(variant: String) -> String {
+    match variant.find('(') {
+        Some(idx) => variant[..idx].to_string(),
+        None => variant,
+    }
+}
+
+// payload type of tx and its message, decoded from tx data so wallet shows readable fields
+fn get_tx_payload(tx: &TxSigningPayload) -> Result<(String, Value, Value), Box<dyn StdError>> {
+    match &tx.tx_type {
+        TxType::Transfer => {
+            let params = get_transfer_payload_params(tx.data.clone())?;
+            Ok((
+                "Transfer".to_string(),
+                json!([
+                    {"name": "to", "type": "address"},
+                    {"name": "tokens", "type": "string[]"},
+                    {"name": "amounts", "type": "string[]"},
+                    {"name": "amount", "type": "uint256"},
+                    {"name": "slippage", "type": "uint256"},
+                    {"name": "refundToken", "type": "string"},
+                    {"name": "dataHash", "type": "bytes32"},
+                ]),
+                json!({
+                    "to": format!("{:?}", params.to),
+                    "tokens": params.tokens,
+                    "amounts": params.data,
+                    "amount": params.amount.to_string(),
+                    "slippage": params.slippage.to_string(),
+                    "refundToken": params.refund_token,
+                    "dataHash": get_data_hash(&tx.data)?,
+                }),
+            ))
+        }
+        TxType::CrosschainTransfer(dst_chain_id) => {
+            let params = get_crosschain_transfer_payload_params(tx.data.clone())?;
+            let mut data: Vec<String> = Vec::new();
+            for entry in params.data.iter() {
+                data.push(to_hex_bytes(entry)?);
+            }
+            Ok((
+                "CrosschainTransfer".to_string(),
+                json!([
+                    {"name": "dstChainId", "type": "string"},
+                    {"name": "recipient", "type": "address"},
+                    {"name": "tokens", "type": "string[]"},
+                    {"name": "data", "type": "bytes[]"},
+                    {"name": "dataHash", "type": "bytes32"},
+                ]),
+                json!({
+                    "dstChainId": dst_chain_id,
+                    "recipient": format!("{:?}", params.recipient),
+                    "tokens": params.tokens,
+                    "data": data,
+                    "dataHash": get_data_hash(&tx.data)?,
+                }),
+            ))
+        }
+        TxType::CrossChainMessage(dst_chain_id) => {
+            let params = get_crosschain_message_payload_params(tx.data.clone())?;
+            Ok((
+                "CrossChainMessage".to_string(),
+                json!([
+                    {"name": "dstChainId", "type": "string"},
+                    {"name": "recipient", "type": "address"},
+                    {"name": "message", "type": "bytes"},
+                    {"name": "dataHash", "type": "bytes32"},
+                ]),
+                json!({
+                    "dstChainId": dst_chain_id,
+                    "recipient": format!("{:?}", params.recipient),
+                    "message": to_hex_bytes(&params.message)?,
+                    "dataHash": get_data_hash(&tx.data)?,
+                }),
+            ))
+        }
+        TxType::UserKYC => {
+            let params = get_kyc_data(tx.data.clone())?;
+            Ok((
+                "UserKYC".to_string(),
+                json!([
+                    {"name": "name", "type": "string"},
+                    {"name": "aadharNo", "type": "string"},
+                    {"name": "upiId", "type": "string"},
+                    {"name": "mobile", "type": "string"},
+                    {"name": "address", "type": "string"},
+                    {"name": "country", "type": "string"},
+                    {"name": "dataHash", "type": "bytes32"},
+                ]),
+                json!({
+                    "name": params.name,
+                    "aadharNo": params.aadhar_no,
+                    "upiId": params.upi_id,
+                    "mobile": params.mobile,
+                    "address": params.address,
+                    "country": params.country,
+                    "dataHash": get_data_hash(&tx.data)?,
+                }),
+            ))
+        }
+        TxType::CrossChainRequest(request_type_data) => Ok((
+            "CrossChainRequest".to_string(),
+            json!([
+                {"name": "requestTypeData", "type": "bytes"},
+                {"name": "data", "type": "bytes"},
+            ]),
+            json!({
+                "requestTypeData": to_hex_bytes(request_type_data)?,
+                "data": to_hex_bytes(&tx.data)?,
+            }),
+        )),
+        TxType::None => Err("tx type none can't be signed".into()),
+        // governance txs carry abi encoded params of proposal they create
+        _ => Ok((
+            tx.tx_type.to_string(),
+            json!([{"name": "data", "type": "bytes"}]),
+            json!({"data": to_hex_bytes(&tx.data)?}),
+        )),
+    }
+}
+
+pub fn get_tx_typed_data(tx: &TxSigningPayload) -> Result<TypedData, Box<dyn StdError>> {
+    let (payload_type, payload_fields, payload) = get_tx_payload(tx)?;
+    let proof_hash = match &tx.proof {
+        Some(proof) => H256::from(keccak256(serde_json::to_vec(proof)?)),
+        None => H256::zero(),
+    };
+    let mut types = json!({
+        "EIP712Domain": get_domain_type(),
+        "Transaction": [
+                {"name": "chainId", "type": "string"},
+                {"name": "createdAt", "type": "uint256"},
+                {"name": "nonce", "type": "uint256"},
+                {"name": "from", "type": "address"},
+                {"name": "txType", "type": "string"},
+                {"name": "payload", "type": payload_type.clone()},
+                {"name": "proofHash", "type": "bytes32"},
+        ],
+    });
+    types
+        .as_object_mut()
+        .unwrap()
+        .insert(payload_type, payload_fields);
+    let typed_data = json!({
+        "types": types,
+        "primaryType": "Transaction",
+        "domain": get_domain(),
+        "message": {
+            "chainId": tx.chain_id,
+            "createdAt": tx.created_at.to_string(),
+            "nonce": tx.nonce.to_string(),
+            "from": format!("{:?}", tx.from),
+            "txType": get_variant_name(tx.tx_type.to_string()),
+            "payload": payload,
+            "proofHash": format!("{:?}", proof_hash),
+        },
+    });
+    parse_typed_data(typed_data, &tx.chain_id)
+}
+
+pub fn get_proposal_typed_data(proposal: &RawProposal) -> Result<TypedData, Box<dyn StdError>> {
+    let type_data = match &proposal.proposal_type {
+        ProposalType::CrossChainRequest(request_type_data) => to_hex_bytes(request_type_data)?,
+        _ => "0x".to_string(),
+    };
+    let extra_data_hash = match &proposal.extra_data {
+        Some(extra_data) => H256::from(keccak256(serde_json::to_vec(
+            &extra_data.get_signing_data(),
+        )?)),
+        None => H256::zero(),
+    };
+    let typed_data = json!({
+        "types": {
+            "EIP712Domain": get_domain_type(),
+            "Proposal": [
+                {"name": "chainId", "type": "string"},
+                {"name": "proposalType", "type": "string"},
+                {"name": "typeData", "type": "bytes"},
+                {"name": "proposedBy", "type": "address"},
+                {"name": "proposedAt", "type": "uint256"},
+                {"name": "nonce", "type": "uint256"},
+                {"name": "data", "type": "bytes"},
+                {"name": "extraDataHash", "type": "bytes32"},
+            ],
+        },
+        "primaryType": "Proposal",
+        "domain": get_domain(),
+        "message": {
+            "chainId": proposal.chain_id,
+            "proposalType": get_variant_name(proposal.proposal_type.to_string()),
+            "typeData": type_data,
+            "proposedBy": format!("{:?}", proposal.proposed_by),
+            "proposedAt": proposal.proposed_at.to_string(),
+            "nonce": proposal.nonce.to_string(),
+            "data": to_hex_bytes(&proposal.data)?,
+            "extraDataHash": format!("{:?}", extra_data_hash),
+        },
+    });
+    parse_typed_data(typed_data, &proposal.chain_id)
+}
+
+// digest signed by eth_signTypedData_v4
+pub fn get_typed_data_digest(typed_data: &TypedData) -> Result<H256, Box<dyn StdError>> {
+    let res = typed_data.encode_eip712();
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    Ok(H256::from(res.unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Address;
+    use cosmwasm_std::Uint128;
+    use ethers::{
+        abi::{encode, Token},
+        types::{Signature, U256},
+    };
+    use std::str::FromStr;
+
+    fn hash_token(data: &[u8]) -> Token {
+        Token::FixedBytes(keccak256(data).to_vec())
+    }
+
+    // keccak256(0x1901 || domain separator || struct hash), encoded by hand as in eip-712
+    fn get_digest(chain_id: &str, struct_hash: [u8; 32]) -> H256 {
+        let domain_separator = keccak256(encode(&[
+            hash_token(b"EIP712Domain(string name,string version,bytes32 salt)"),
+            hash_token(Eip712DomainName.as_bytes()),
+            hash_token(Eip712DomainVersion.as_bytes()),
+            hash_token(chain_id.as_bytes()),
+        ]));
+        let mut data = vec![0x19u8, 0x01u8];
+        data.extend(domain_separator);
+        data.extend(struct_hash);
+        H256::from(keccak256(data))
+    }
+
+    #[test]
+    fn matches_eip712_spec_vector() {
+        // Mail example of eip-712
+        let typed_data: TypedData = serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"},
+                ],
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"},
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"},
+                ],
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+            },
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!",
+            },
+        }))
+        .unwrap();
+        assert_eq!(
+            get_typed_data_digest(&typed_data).unwrap(),
+            H256::from_str("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn encodes_tx_typed_data() {
+        let request_type_data = HexString::from_vec(vec![1u8, 2u8, 3u8]);
+        let tx = TxSigningPayload {
+            chain_id: "digichain".to_string(),
+            created_at: 1700000000u64,
+            nonce: Uint128::from(5u128),
+            from: Address::from_low_u64_be(9u64),
+            tx_type: TxType::CrossChainRequest(request_type_data.clone()),
+            data: HexString::from_vec(vec![4u8, 5u8]),
+            proof: None,
+        };
+        let payload_hash = keccak256(encode(&[
+            hash_token(b"CrossChainRequest(bytes requestTypeData,bytes data)"),
+            hash_token(&[1u8, 2u8, 3u8]),
+            hash_token(&[4u8, 5u8]),
+        ]));
+        let struct_hash = keccak256(encode(&[
+            hash_token(b"Transaction(string chainId,uint256 createdAt,uint256 nonce,address from,string txType,CrossChainRequest payload,bytes32 proofHash)CrossChainRequest(bytes requestTypeData,bytes data)"),
+            hash_token(b"digichain"),
+            Token::Uint(U256::from(1700000000u64)),
+            Token::Uint(U256::from(5u64)),
+            Token::Address(tx.from),
+            hash_token(b"CrossChainRequest"),
+            Token::FixedBytes(payload_hash.to_vec()),
+            Token::FixedBytes(H256::zero().as_bytes().to_vec()),
+        ]));
+        let typed_data = get_tx_typed_data(&tx).unwrap();
+        assert_eq!(
+            get_typed_data_digest(&typed_data).unwrap(),
+            get_digest("digichain", struct_hash)
+        );
+
+        // digichain chain id is bound through salt
+        let mut other = tx.clone();
+        other.chain_id = "other".to_string();
+        assert_ne!(
+            get_typed_data_digest(&get_tx_typed_data(&other).unwrap()).unwrap(),
+            get_typed_data_digest(&typed_data).unwrap()
+        );
+        other = tx.clone();
+        other.tx_type = TxType::None;
+        assert!(get_tx_typed_data(&other).is_err());
+
+        // wallets get salt as hex string
+        assert_eq!(
+            get_wallet_typed_data(&typed_data)["domain"]["salt"],
+            json!(format!("{:?}", H256::from(keccak256(b"digichain"))))
+        );
+    }
+
+    #[test]
+    fn encodes_proposal_typed_data() {
+        let proposal = RawProposal {
+            hash: String::new(),
+            chain_id: "digichain".to_string(),
+            proposal_type: ProposalType::CrossChainRequest(HexString::from_vec(vec![1u8])),
+            proposed_by: Address::from_low_u64_be(9u64),
+            proposed_at: 12u64,
+            data: HexString::from_vec(vec![2u8, 3u8]),
+            nonce: Uint128::from(4u128),
+            signature: Signature {
+                r: U256::zero(),
+                s: U256::zero(),
+                v: 0u64,
+            },
+            extra_data: None,
+        };
+        let struct_hash = keccak256(encode(&[
+            hash_token(b"Proposal(string chainId,string proposalType,bytes typeData,address proposedBy,uint256 proposedAt,uint256 nonce,bytes data,bytes32 extraDataHash)"),
+            hash_token(b"digichain"),
+            hash_token(b"CrossChainRequest"),
+            hash_token(&[1u8]),
+            Token::Address(proposal.proposed_by),
+            Token::Uint(U256::from(12u64)),
+            Token::Uint(U256::from(4u64)),
+            hash_token(&[2u8, 3u8]),
+            Token::FixedBytes(H256::zero().as_bytes().to_vec()),
+        ]));
+        assert_eq!(
+            get_typed_data_digest(&get_proposal_typed_data(&proposal).unwrap()).unwrap(),
+            get_digest("digichain", struct_hash)
+        );
+    }
+}
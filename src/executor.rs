@@ -0,0 +1,618 @@
+use crate::{
+    chain_adapter::{ChainAdapter, EvmAdapter},
+    constants::BatchWithdrawRequest,
+    crosschain::CrossChainRequestStatus,
+    digichain::DigiChain,
+    proposal::CrossChainWithdrawMsg,
+    relayer::{encode_withdraw_executed_data, get_signed_transaction, WithdrawExecutedEvent},
+    transaction::{Transaction, TxType},
+    types::Address,
+    utils::encode_crosschain_request_type_data,
+};
+use async_trait::async_trait;
+use cosmwasm_std::Uint128;
+use ethers::{
+    contract::{abigen, parse_log, EthEvent},
+    providers::Middleware,
+    types::{Bytes, H256, U256 as EthU256, U64},
+};
+use std::{
+    collections::HashMap, error::Error as StdError, str::FromStr, sync::Arc, time::Duration,
+};
+
+abigen!(
+    GatewayContract,
+    r#"[
+        function executeWithdraw(string srcChainId, string dstChainId, uint256 srcNonce, bytes payload, bytes[] sigs) external
+        function executeWithdrawBatch(string srcChainId, string dstChainId, uint256 batchNonce, bytes payload, bytes[] sigs, uint256[] srcNonces, bytes[] payloads) external
+    ]"#
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawReceipt {
+    pub success: bool,
+    pub block_number: u64,
+    pub dst_nonce: Uint128, // gateway nonce from WithdrawExecuted event
+}
+
+// access to gateway of a dst chain, stand-in implementations can be used in place of provider
+#[async_trait]
+pub trait WithdrawGateway: Send + Sync {
+    // submits withdraw msg to gateway, batch msg pays all its withdraws in same call,
+    // returns hash of tx on dst chain
+    async fn execute_withdraw(
+        &self,
+        gateway: Address,
+        withdraw: &CrossChainWithdrawMsg,
+    ) -> Result<H256, Box<dyn StdError + Send + Sync>>;
+
+    // none while tx is not mined yet
+    async fn get_withdraw_receipt(
+        &self,
+        gateway: Address,
+        tx_hash: H256,
+    ) -> Result<Option<WithdrawReceipt>, Box<dyn StdError + Send + Sync>>;
+}
+
+// gateway on evm chain, client should sign with validator wallet
+#[derive(Clone, Debug)]
+pub struct EvmGateway<M: Middleware> {
+    pub client: Arc<M>,
+}
+
+impl<M: Middleware> EvmGateway<M> {
+    pub fn new(client: Arc<M>) -> EvmGateway<M> {
+        EvmGateway { client }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware + 'static> WithdrawGateway for EvmGateway<M> {
+    async fn execute_withdraw(
+        &self,
+        gateway: Address,
+        withdraw: &CrossChainWithdrawMsg,
+    ) -> Result<H256, Box<dyn StdError + Send + Sync>> {
+        let res = withdraw.payload.to_vec();
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        let payload = res.unwrap();
+        let sigs = withdraw
+            .sigs
+            .iter()
+            .map(|sig| Bytes::from(EvmAdapter.encode_signature(sig)))
+            .collect::<Vec<Bytes>>();
+        let contract = GatewayContract::new(gateway, self.client.clone());
+        if withdraw.entries.len() > 0 {
+            let mut src_nonces: Vec<EthU256> = Vec::new();
+            let mut payloads: Vec<Bytes> = Vec::new();
+            for (src_nonce, payload) in withdraw.entries.iter() {
+                let res = payload.to_vec();
+                if res.is_err() {
+                    return Err(format!("{:?}", res.err()).into());
+                }
+                src_nonces.push(EthU256::from(src_nonce.u128()));
+                payloads.push(Bytes::from(res.unwrap()));
+            }
+            let call = contract.execute_withdraw_batch(
+                withdraw.src_chain_id.clone(),
+                withdraw.dst_chain_id.clone(),
+                EthU256::from(withdraw.src_nonce.u128()),
+                Bytes::from(payload),
+                sigs,
+                src_nonces,
+                payloads,
+            );
+            // pending tx borrows call, only its hash is kept
+            let res = call.send().await.map(|pending| pending.tx_hash());
+            if res.is_err() {
+                return Err(format!("executeWithdrawBatch: {:?}", res.err()).into());
+            }
+            return Ok(res.unwrap());
+        }
+        let call = contract.execute_withdraw(
+            withdraw.src_chain_id.clone(),
+            withdraw.dst_chain_id.clone(),
+            EthU256::from(withdraw.src_nonce.u128()),
+            Bytes::from(payload),
+            sigs,
+        );
+        let res = call.send().await.map(|pending| pending.tx_hash());
+        if res.is_err() {
+            return Err(format!("executeWithdraw: {:?}", res.err()).into());
+        }
+        Ok(res.unwrap())
+    }
+
+    async fn get_withdraw_receipt(
+        &self,
+        gateway: Address,
+        tx_hash: H256,
+    ) -> Result<Option<WithdrawReceipt>, Box<dyn StdError + Send + Sync>> {
+        let res = self.client.get_transaction_receipt(tx_hash).await;
+        if res.is_err() {
+            return Err(format!("get_transaction_receipt: {:?}", res.err()).into());
+        }
+        let res = res.unwrap();
+        if res.is_none() {
+            return Ok(None);
+        }
+        let receipt = res.unwrap();
+        let event = receipt
+            .logs
+            .iter()
+            .filter(|log| {
+                log.address == gateway
+                    && log.topics.first() == Some(&WithdrawExecutedEvent::signature())
+            })
+            .find_map(|log| parse_log::<WithdrawExecutedEvent>(log.clone()).ok());
+        let mut dst_nonce = Uint128::zero();
+        if let Some(event) = event {
+            let res = u128::try_from(event.nonce);
+            if res.is_err() {
+                return Err(format!("WithdrawExecuted: nonce out of range").into());
+            }
+            dst_nonce = Uint128::from(res.unwrap());
+        }
+        Ok(Some(WithdrawReceipt {
+            success: receipt.status == Some(U64::from(1u64)),
+            block_number: receipt.block_number.map_or(0u64, |number| number.as_u64()),
+            dst_nonce,
+        }))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingWithdraw {
+    pub withdraw: CrossChainWithdrawMsg,
+    pub gateway: Address,
+    pub dst_tx_hash: H256,
+}
+
+// executes withdraws assigned to validator of this node on dst chains and replies once mined
+pub struct Executor<G: WithdrawGateway> {
+    pub poll_interval: u64,
+    pub gateways: HashMap<String, G>, // dst chain_id -> gateway client of that chain
+    pub pending: HashMap<(String, String), PendingWithdraw>, // (src_chain_id,nonce) -> submitted, waiting to be mined
+}
+
+impl<G: WithdrawGateway> Executor<G> {
+    pub fn new(poll_interval: u64) -> Executor<G> {
+        Executor {
+            poll_interval,
+            gateways: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn add_gateway(&mut self, chain_id: String, gateway: G) {
+        self.gateways.insert(chain_id, gateway);
+    }
+
+    pub async fn run(&mut self, digichain: DigiChain) {
+        loop {
+            if !digichain.pause.read().unwrap().clone() {
+                self.execute_withdraws(&digichain).await;
+                self.reply_executed_withdraws(&digichain).await;
+            }
+            tokio::time::sleep(Duration::from_secs(self.poll_interval)).await;
+        }
+    }
+
+    // submits assigned withdraws not executed yet, returns no of submitted withdraws
+    pub async fn execute_withdraws(&mut self, digichain: &DigiChain) -> usize {
+        let validator = digichain.validator.read().unwrap().acccount.address;
+        let withdraws = digichain
+            .mempool
+            .read()
+            .unwrap()
+            .get_crosschain_request_to_execute(validator);
+        let mut submitted = 0usize;
+        for withdraw in withdraws {
+            let key = (
+                withdraw.src_chain_id.clone(),
+                withdraw.src_nonce.to_string(),
+            );
+            if self.pending.contains_key(&key) {
+                continue;
+            }
+            // executed earlier, reply is submitted or gateway rejected it
+            if digichain.crosschain.read().unwrap().is_broadcasted(
+                validator,
+                withdraw.src_chain_id.clone(),
+                withdraw.src_nonce,
+            ) {
+                continue;
+            }
+            let gateway = self.get_gateway_address(digichain, withdraw.dst_chain_id.clone());
+            if gateway.is_none() {
+                continue;
+            }
+            let gateway = gateway.unwrap();
+            let client = self.gateways.get(&withdraw.dst_chain_id).unwrap();
+            let res = client.execute_withdraw(gateway, &withdraw).await;
+            if res.is_err() {
+                println!(
+                    "Withdraw Execution Failed || DstChainId: {}, SrcNonce: {}, Error: {:?}",
+                    withdraw.dst_chain_id,
+                    withdraw.src_nonce,
+                    res.err()
+                );
+                continue;
+            }
+            let dst_tx_hash = res.unwrap();
+            {
+                let block_number = digichain.get_block_number();
+                let mut crosschain = digichain.crosschain.write().unwrap();
+                let _ = crosschain.broadcasted(
+                    validator,
+                    withdraw.src_chain_id.clone(),
+                    withdraw.src_nonce,
+                );
+                crosschain.update_request_status(
+                    withdraw.src_chain_id.clone(),
+                    withdraw.src_nonce,
+                    CrossChainRequestStatus::Broadcasted,
+                    block_number,
+                );
+            }
+            println!(
+                "Withdraw Executed || DstChainId: {}, SrcNonce: {}, DstTxHash: {:?}",
+                withdraw.dst_chain_id, withdraw.src_nonce, dst_tx_hash
+            );
+            self.pending.insert(
+                key,
+                PendingWithdraw {
+                    withdraw,
+                    gateway,
+                    dst_tx_hash,
+                },
+            );
+            submitted = submitted + 1usize;
+        }
+        submitted
+    }
+
+    // submits reply CrossChainRequest of withdraws mined on dst chain, returns no of replies
+    pub async fn reply_executed_withdraws(&mut self, digichain: &DigiChain) -> usize {
+        let mut replied = 0usize;
+        for (key, pending) in self.pending.clone() {
+            let res = self.gateways.get(&pending.withdraw.dst_chain_id);
+            if res.is_none() {
+                continue;
+            }
+            let res = res
+                .unwrap()
+                .get_withdraw_receipt(pending.gateway, pending.dst_tx_hash)
+                .await;
+            if res.is_err() {
+                println!(
+                    "Withdraw Receipt Failed || DstTxHash: {:?}, Error: {:?}",
+                    pending.dst_tx_hash,
+                    res.err()
+                );
+                continue;
+            }
+            let res = res.unwrap();
+            if res.is_none() {
+                continue;
+            }
+            let receipt = res.unwrap();
+            self.pending.remove(&key);
+            if !receipt.success {
+                // not executed on dst chain, validator executes it again unless it gets reassigned
+                let validator = digichain.validator.read().unwrap().acccount.address;
+                let block_number = digichain.get_block_number();
+                let mut crosschain = digichain.crosschain.write().unwrap();
+                crosschain.clear_broadcasted(
+                    validator,
+                    pending.withdraw.src_chain_id.clone(),
+                    pending.withdraw.src_nonce,
+                );
+                crosschain.update_request_status(
+                    pending.withdraw.src_chain_id.clone(),
+                    pending.withdraw.src_nonce,
+                    CrossChainRequestStatus::AssignedToValidator,
+                    block_number,
+                );
+                println!(
+                    "Withdraw Reverted || DstChainId: {}, SrcNonce: {}, DstTxHash: {:?}",
+                    pending.withdraw.dst_chain_id, pending.withdraw.src_nonce, pending.dst_tx_hash
+                );
+                continue;
+            }
+            let res = get_reply_transaction(digichain, &pending, &receipt);
+            if res.is_err() {
+                println!(
+                    "Withdraw Reply Failed || SrcNonce: {}, Error: {:?}",
+                    pending.withdraw.src_nonce,
+                    res.err()
+                );
+                continue;
+            }
+            // already marked broadcasted while executing withdraw
+            let tx = res.unwrap();
+            let account_nonce = digichain
+                .get_account(tx.from)
+                .map_or(Uint128::zero(), |account| account.tx_nonce);
+            let res = digichain
+                .mempool
+                .write()
+                .unwrap()
+                .add_transaction(&tx, account_nonce);
+            if res.is_err() {
+                println!(
+                    "Withdraw Reply Failed || SrcNonce: {}, Error: {:?}",
+                    pending.withdraw.src_nonce,
+                    res.err()
+                );
+                continue;
+            }
+            replied = replied + 1usize;
+        }
+        replied
+    }
+
+    fn get_gateway_address(&self, digichain: &DigiChain, chain_id: String) -> Option<Address> {
+        if !self.gateways.contains_key(&chain_id) {
+            return None;
+        }
+        let res = digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .get_contracts_config(vec![chain_id.clone()])
+            .remove(&chain_id);
+        if res.is_none() {
+            return None;
+        }
+        let contract_config = res.unwrap();
+        if contract_config.disabled {
+            return None;
+        }
+        Address::from_str(&contract_config.contract_address).ok()
+    }
+}
+
+// reply of mined withdraw, request type is same as of withdraw payload
+fn get_reply_transaction(
+    digichain: &DigiChain,
+    pending: &PendingWithdraw,
+    receipt: &WithdrawReceipt,
+) -> Result<Transaction, Box<dyn StdError>> {
+    // batch payload only carries merkle root, its withdraws are acked along with it
+    if pending.withdraw.entries.len() > 0 {
+        let validator = digichain.validator.read().unwrap().acccount.address;
+        let request_type_data = encode_crosschain_request_type_data(
+            BatchWithdrawRequest,
+            pending.withdraw.src_chain_id.clone(),
+            pending.withdraw.src_nonce,
+            pending.withdraw.dst_chain_id.clone(),
+            receipt.dst_nonce,
+            validator,
+        );
+        let data = encode_withdraw_executed_data(
+            pending.withdraw.src_chain_id.clone(),
+            pending.withdraw.dst_chain_id.clone(),
+            pending.gateway,
+            Address::zero(),
+            Address::zero(),
+            vec![],
+            vec![],
+            pending.withdraw.src_nonce,
+            receipt.dst_nonce,
+            receipt.block_number,
+            format!("{:?}", pending.dst_tx_hash),
+        );
+        return get_signed_transaction(
+            digichain,
+            TxType::CrossChainRequest(request_type_data),
+            data,
+        );
+    }
+    let res = digichain
+        .crosschain
+        .read()
+        .unwrap()
+        .get_chain_adapter(pending.withdraw.dst_chain_id.clone())
+        .and_then(|adapter| adapter.decode_withdraw_payload(&pending.withdraw.payload));
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    let payload = res.unwrap();
+    let validator = digichain.validator.read().unwrap().acccount.address;
+    let request_type_data = encode_crosschain_request_type_data(
+        payload.request_type,
+        pending.withdraw.src_chain_id.clone(),
+        pending.withdraw.src_nonce,
+        pending.withdraw.dst_chain_id.clone(),
+        receipt.dst_nonce,
+        validator,
+    );
+    let data = encode_withdraw_executed_data(
+        pending.withdraw.src_chain_id.clone(),
+        pending.withdraw.dst_chain_id.clone(),
+        pending.gateway,
+        payload.recipient,
+        payload.depositor,
+        payload.tokens,
+        payload.amounts,
+        pending.withdraw.src_nonce,
+        receipt.dst_nonce,
+        receipt.block_number,
+        format!("{:?}", pending.dst_tx_hash),
+    );
+    get_signed_transaction(
+        digichain,
+        TxType::CrossChainRequest(request_type_data),
+        data,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_adapter::ChainType,
+        constants::{ChainTypeEvm, UnLockedWithdrawRequest},
+        crosschain::CrossChainExecutionResult,
+        utils::get_crosschain_withdraw_payload,
+    };
+    use ethers::signers::Signer;
+    use std::sync::Mutex;
+
+    // gateway of dst chain kept in memory, every submitted withdraw gets mined at once
+    struct StandInGateway {
+        success: bool,
+        executed: Mutex<Vec<Uint128>>,
+    }
+
+    impl StandInGateway {
+        fn new(success: bool) -> StandInGateway {
+            StandInGateway {
+                success,
+                executed: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WithdrawGateway for StandInGateway {
+        async fn execute_withdraw(
+            &self,
+            _gateway: Address,
+            withdraw: &CrossChainWithdrawMsg,
+        ) -> Result<H256, Box<dyn StdError + Send + Sync>> {
+            let mut executed = self.executed.lock().unwrap();
+            executed.push(withdraw.src_nonce);
+            Ok(H256::from_low_u64_be(executed.len() as u64))
+        }
+
+        async fn get_withdraw_receipt(
+            &self,
+            _gateway: Address,
+            _tx_hash: H256,
+        ) -> Result<Option<WithdrawReceipt>, Box<dyn StdError + Send + Sync>> {
+            Ok(Some(WithdrawReceipt {
+                success: self.success,
+                block_number: 10u64,
+                dst_nonce: Uint128::from(7u128),
+            }))
+        }
+    }
+
+    // chain with one withdraw to ethereum assigned to validator of this node
+    fn get_digichain() -> DigiChain {
+        let mut digichain = DigiChain::default();
+        digichain.chain_id = "digichain".to_string();
+        let validator = {
+            let mut validator = digichain.validator.write().unwrap();
+            validator.acccount.address = validator.wallet.address();
+            validator.acccount.address
+        };
+        let src_nonce = Uint128::from(1u128);
+        let payload = get_crosschain_withdraw_payload(
+            UnLockedWithdrawRequest,
+            vec![Address::from_low_u64_be(3u64)],
+            vec![Uint128::from(100u128)],
+            Address::from_low_u64_be(4u64),
+            Address::from_low_u64_be(5u64),
+            vec![],
+        );
+        let withdraw = CrossChainWithdrawMsg::new(
+            "ethereum".to_string(),
+            digichain.chain_id.clone(),
+            src_nonce,
+            payload.clone(),
+            digichain.get_validator_set_id(),
+            ChainType::Evm,
+        );
+        {
+            let mut crosschain = digichain.crosschain.write().unwrap();
+            crosschain
+                .add_contract_config(
+                    "ethereum".to_string(),
+                    format!("{:?}", Address::from_low_u64_be(2u64)),
+                    0u64,
+                    ChainTypeEvm,
+                )
+                .unwrap();
+            let mut request = CrossChainExecutionResult::new(String::new());
+            request.dst_chain_id = Some("ethereum".to_string());
+            request.payload = Some(payload);
+            request.update_status(CrossChainRequestStatus::AssignedToValidator, 0u64);
+            crosschain.add_request(digichain.chain_id.clone(), src_nonce, request);
+        }
+        digichain
+            .mempool
+            .write()
+            .unwrap()
+            .add_crosschain_request(validator, &withdraw);
+        digichain
+    }
+
+    fn get_status(digichain: &DigiChain) -> CrossChainRequestStatus {
+        let request = digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .get_request(digichain.chain_id.clone(), Uint128::from(1u128))
+            .unwrap();
+        request.status_history.last().unwrap().0.clone()
+    }
+
+    #[actix_web::test]
+    async fn executes_withdraw_and_replies_once_mined() {
+        let digichain = get_digichain();
+        let validator = digichain.validator.read().unwrap().acccount.address;
+        let mut executor = Executor::new(1u64);
+        executor.add_gateway("ethereum".to_string(), StandInGateway::new(true));
+
+        assert_eq!(executor.execute_withdraws(&digichain).await, 1usize);
+        assert_eq!(get_status(&digichain), CrossChainRequestStatus::Broadcasted);
+        assert!(digichain.crosschain.read().unwrap().is_broadcasted(
+            validator,
+            digichain.chain_id.clone(),
+            Uint128::from(1u128)
+        ));
+        // submitted once only
+        assert_eq!(executor.execute_withdraws(&digichain).await, 0usize);
+
+        assert_eq!(executor.reply_executed_withdraws(&digichain).await, 1usize);
+        assert!(executor.pending.is_empty());
+        let mempool = digichain.mempool.read().unwrap();
+        assert_eq!(mempool.transactions.len(), 1usize);
+        let tx = mempool.transactions[0].clone();
+        assert_eq!(tx.from, validator);
+        match tx.tx_type {
+            TxType::CrossChainRequest(_) => {}
+            _ => panic!("reply is not a CrossChainRequest tx"),
+        }
+    }
+
+    #[actix_web::test]
+    async fn reverted_withdraw_gets_executed_again() {
+        let digichain = get_digichain();
+        let validator = digichain.validator.read().unwrap().acccount.address;
+        let mut executor = Executor::new(1u64);
+        executor.add_gateway("ethereum".to_string(), StandInGateway::new(false));
+
+        assert_eq!(executor.execute_withdraws(&digichain).await, 1usize);
+        assert_eq!(executor.reply_executed_withdraws(&digichain).await, 0usize);
+        assert!(!digichain.crosschain.read().unwrap().is_broadcasted(
+            validator,
+            digichain.chain_id.clone(),
+            Uint128::from(1u128)
+        ));
+        assert_eq!(
+            get_status(&digichain),
+            CrossChainRequestStatus::AssignedToValidator
+        );
+        assert!(digichain.mempool.read().unwrap().transactions.is_empty());
+
+        assert_eq!(executor.execute_withdraws(&digichain).await, 1usize);
+        let gateway = executor.gateways.get("ethereum").unwrap();
+        assert_eq!(gateway.executed.lock().unwrap().len(), 2usize);
+    }
+}
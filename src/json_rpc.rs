@@ -2,12 +2,15 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex, MutexGuard};
 
 use crate::acccount::Account;
+use crate::crosschain::CrossChainRequestStatus;
 use crate::digichain::DigiChain;
+use crate::eip712::{get_tx_typed_data, get_typed_data_digest, get_wallet_typed_data};
 use crate::proposal::Proposal;
 use crate::token::DigiToken;
 use crate::transaction::{TxType, Transaction};
-use crate::types::{BroadcastTransactionParams, GetAccountParams, GetChainParams, GetTokenParams, GetTokensParams, GetTokenByChain, GetConfigParams, GetCrossChainRequestsParams, GetProposalsParams, GetBalanceOf, Address, GetTransactionParams, GetBalances, GetCrossChainRequestReadyToExecute, UpdateTokensPriceParams, GetOptimalPath, AirDropParams, PauseAndUnPauseParams, IsBroadcastedParams, GetTransactionsParams};
-use crate::utils::{decode_crosschain_request_type_data, abs};
+use crate::types::{BroadcastProposalParams, BroadcastTransactionParams, GetAccountParams, GetChainParams, GetTokenParams, GetTokensParams, GetTokenByChain, GetConfigParams, GetCrossChainRequestsParams, GetProposalsParams, GetBalanceOf, Address, GetTransactionParams, GetBalances, GetCrossChainRequestReadyToExecute, UpdateTokensPriceParams, GetOptimalPath, AirDropParams, PauseAndUnPauseParams, IsBroadcastedParams, GetTransactionsParams, HexString, GetCrossChainRequestStatusParams, GetInboundNonceGapsParams, GetBridgeReservesParams, GetLightClientHeadParams, GetWithdrawBatchProofParams, GetTypedDataParams};
+use crate::utils::{decode_crosschain_request_type_data, get_merkle_proof, get_merkle_root, abs};
+use ethers::types::H256;
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use jsonrpc_http_server::jsonrpc_core::Value;
 
@@ -108,6 +111,13 @@ impl JsonRpc {
                 .into();
         }
         let params: BroadcastTransactionParams = res.unwrap();
+        // anyone can submit tx, it is only accepted if it is signed by tx.from
+        let res = params.transaction.verify_signature();
+        if res.is_err() {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": format!("{:?}", res.err()) }))
+                .into();
+        }
         let mut tx = params.transaction.clone().to_transaction();
         // TODO: add validation like is tc.from is validator or not?
         if tx.tx_type != TxType::UserKYC {
@@ -123,39 +133,125 @@ impl JsonRpc {
                 .into();
             }
         } 
-        // if tx_type is crosschain request then mark src_chain_id,src_nonce is broadcasted by validator
-        let mut variant = tx.tx_type.to_string();
-        if let Some(s) = variant.find('(') {
-            variant = variant[..s].to_string();
-        }
-        // if crosschain request to other chain
-        if variant == "CrossChainRequest".to_string() {
-           match &tx.tx_type {
-                TxType::CrossChainRequest(hex_str) => {
-                    //TODO: sender must be validator
-                   let decoded_info =  decode_crosschain_request_type_data(hex_str);
-                   if decoded_info.is_err(){
-                    return HttpResponse::BadRequest().json(json!({"error":"Error While Decoding Request Type Data"}));
-                   }
-                   let decoded_info = decoded_info.unwrap();
-                   let mut binding = digichain.crosschain.write().unwrap();
-                   let res= binding.broadcasted(tx.from, decoded_info.src_chain_id, decoded_info.src_nonce);
-                   if res.is_err() {
-                     return HttpResponse::AlreadyReported().json(json!({"error":format!("{:?}",res.err())}));
-                   }
-                }
-                _ => {} // it will not reach here
-            };
-          
+        // stale and duplicate nonces are rejected before request is marked broadcasted
+        let account_nonce = digichain
+            .get_account(tx.from)
+            .map_or(Uint128::zero(), |account| account.tx_nonce);
+        let res = digichain.mempool.read().unwrap().check_tx_nonce(&tx, account_nonce);
+        if res.is_err() {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": format!("{:?}", res.err()) }))
+                .into();
+        }
+        // if tx_type is crosschain request then src_chain_id,src_nonce is marked broadcasted by validator
+        // once tx is in mempool, same as relayer does
+        let mut decoded_info = None;
+        if let TxType::CrossChainRequest(hex_str) = &tx.tx_type {
+            //TODO: sender must be validator
+            let res = decode_crosschain_request_type_data(hex_str);
+            if res.is_err() {
+                return HttpResponse::BadRequest().json(json!({"error":"Error While Decoding Request Type Data"}));
+            }
+            let res = res.unwrap();
+            if digichain
+                .crosschain
+                .read()
+                .unwrap()
+                .is_broadcasted_by_any(res.src_chain_id.clone(), res.src_nonce)
+            {
+                return HttpResponse::AlreadyReported().json(json!({"error":"Already Broadcasted"}));
+            }
+            decoded_info = Some(res);
         }
         tx.hash = tx.calculate_hash();
-        digichain
+        let res = digichain
             .mempool
             .write()
             .unwrap()
-            .add_transaction(&tx);
+            .add_transaction(&tx, account_nonce);
+        if res.is_err() {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": format!("{:?}", res.err()) }))
+                .into();
+        }
+        if let Some(decoded_info) = decoded_info {
+            let block_number = digichain.get_block_number();
+            let mut binding = digichain.crosschain.write().unwrap();
+            let broadcasted = binding.broadcasted(tx.from, decoded_info.src_chain_id.clone(), decoded_info.src_nonce);
+            if broadcasted.is_err() {
+                return HttpResponse::AlreadyReported().json(json!({"error":format!("{:?}",broadcasted.err())}));
+            }
+            binding.update_request_status(decoded_info.src_chain_id, decoded_info.src_nonce, CrossChainRequestStatus::Broadcasted, block_number);
+        }
+        // future nonce waits in mempool until txs before it arrive
+        return HttpResponse::Ok().json(json!({ "data": {
+            "tx_hash":tx.hash,
+            "queued":!res.unwrap()
+        },"id":self.id}));
+    }
+
+    // proposals relayed between nodes, accepted once signed by proposer or by a validator
+    pub fn broadcast_proposal(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<BroadcastProposalParams, serde_json::Error> =
+            serde_json::from_value(request_body.params.clone());
+        if res.is_err() {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": format!("{:?}", res.err()) }))
+                .into();
+        }
+        let params: BroadcastProposalParams = res.unwrap();
+        if params.proposal.chain_id != digichain.chain_id {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": "proposal not for this chain" }))
+                .into();
+        }
+        // same signer rule as Proposal::is_valid, else proposals accepted here fail at execution
+        let res = params.proposal.get_signer();
+        if res.is_err() {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": format!("{:?}", res.err()) }))
+                .into();
+        }
+        let signer = res.unwrap();
+        if signer != params.proposal.proposed_by && !digichain.is_validator(signer) {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": "proposal not signed by proposer or validator" }))
+                .into();
+        }
+        let mut proposal = params.proposal.to_proposal();
+        proposal.block_number = digichain.get_block_number();
+        proposal.hash = proposal.calculate_hash();
+        // executed already, replaying it would execute it again
+        if digichain
+            .index_proposals
+            .read()
+            .unwrap()
+            .contains_key(&proposal.hash)
+        {
+            return HttpResponse::AlreadyReported()
+                .json(json!({ "error": "proposal already executed", "id": self.id }));
+        }
+        let mut mempool = digichain.mempool.write().unwrap();
+        if mempool
+            .get_proposals()
+            .iter()
+            .any(|p| p.hash == proposal.hash)
+        {
+            return HttpResponse::AlreadyReported()
+                .json(json!({ "error": "proposal already present", "id": self.id }));
+        }
+        let res = mempool.add_proposal(proposal.proposal_type.clone(), proposal.clone());
+        if res.is_err() {
+            return HttpResponse::BadRequest()
+                .json(json!({ "err": format!("{:?}", res.err()) }))
+                .into();
+        }
         return HttpResponse::Ok().json(json!({ "data": {
-            "tx_hash":tx.hash
+            "proposal_hash": proposal.hash
         },"id":self.id}));
     }
 
@@ -222,6 +318,7 @@ impl JsonRpc {
         return HttpResponse::Ok().json(json!({ "account": {
                 "address":res.address,
                 "tx_nonce":res.tx_nonce,
+                "pending_tx_nonce":digichain.mempool.read().unwrap().get_pending_nonce(res.address, res.tx_nonce),
                 "proposal_nonce":res.proposal_nonce ,
                 "is_kyc_done":res.is_kyc_done ,
                 "name":res.name ,
@@ -436,13 +533,17 @@ impl JsonRpc {
         #[derive(Serialize, Deserialize)]
         struct  ValidatorResponse {
             pub address:Address,
-            pub staked: Uint128
+            pub staked: Uint128,
+            pub bls_public_key: Option<HexString>,
+            pub bls_pop: Option<HexString>
         }
         let validators : Vec<ValidatorResponse> =
         binding.clone().into_iter().map( |val| {
             ValidatorResponse {
                 address: val.acccount.address,
-                staked: val.staked
+                staked: val.staked,
+                bls_public_key: val.bls_public_key,
+                bls_pop: val.bls_pop
             }
         }).collect::<Vec<ValidatorResponse>>();
         return HttpResponse::Ok().json(json!({ 
@@ -505,11 +606,81 @@ impl JsonRpc {
             }
             res = res.get(from..to).unwrap().to_vec();
         }
+        // each request carries sigs, signers[i] signed sigs[i] and validator_set_id of signers
         return HttpResponse::Ok().json(json!({ 
-            "crosschain_withdraw_requests":  res
+            "crosschain_withdraw_requests":  res,
+            "validator_set_id": digichain.get_validator_set_id()
       ,"id":self.id })); 
     } 
 
+    // explorer view over all crosschain requests known to this chain, [from,to) of filtered requests
+    pub fn query_crosschain_requests(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetCrossChainRequestsParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetCrossChainRequestsParams = res.unwrap();
+        let binding = digichain.crosschain.read().unwrap();
+        let requests = binding.get_requests(params.src_chain_id, params.src_nonce, params.dest_chain_id);
+        let total = requests.len();
+        let mut from = params.from.unwrap_or(0u64) as usize;
+        let mut to = params.to.unwrap_or(total as u64) as usize;
+        if to > total {
+            to = total;
+        }
+        if from > to {
+            from = to;
+        }
+        let res: Vec<Value> = requests[from..to]
+            .iter()
+            .map(|(src_chain_id, src_nonce, request)| {
+                let mut payload = Value::Null;
+                if let Some(data) = &request.payload {
+                    // payload and addresses are in format of dst chain
+                    let decoded = binding
+                        .get_chain_adapter(request.dst_chain_id.clone().unwrap_or_default())
+                        .and_then(|adapter| Ok((adapter.decode_withdraw_payload(data)?, adapter)));
+                    if decoded.is_ok() {
+                        let (decoded, adapter) = decoded.unwrap();
+                        payload = json!({
+                            "request_type": decoded.request_type,
+                            "tokens": decoded.tokens.iter().map(|token| adapter.format_address(*token)).collect::<Vec<String>>(),
+                            "amounts": decoded.amounts,
+                            "depositor": decoded.depositor,
+                            "recipient": adapter.format_address(decoded.recipient),
+                            "message": decoded.message,
+                        });
+                    }
+                }
+                json!({
+                    "src_chain_id": src_chain_id,
+                    "src_nonce": src_nonce,
+                    "dst_chain_id": request.dst_chain_id,
+                    "src_tx_hash": request.src_tx_hash,
+                    "dst_tx_hash": request.dst_tx_hash,
+                    "ack_tx_hash": request.ack_tx_hash,
+                    "status": request.get_status(),
+                    "tokens": request.tokens,
+                    "refund_nonce": request.refund_nonce,
+                    "refund_of": request.refund_of,
+                    "batch_nonce": request.batch_nonce,
+                    "batch_entries": request.batch_entries,
+                    "payload": payload,
+                })
+            })
+            .collect();
+        return HttpResponse::Ok().json(json!({
+            "data": {
+                "requests": res,
+                "total": total,
+            }
+      ,"id":self.id }));
+    }
+
     pub fn get_optimal_path(
         self, 
         digichain: MutexGuard<'_, DigiChain>,
@@ -602,6 +773,240 @@ impl JsonRpc {
       ,"id":self.id }));
     }
 
+    pub fn get_crosschain_request_status(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetCrossChainRequestStatusParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetCrossChainRequestStatusParams = res.unwrap();
+        let binding = digichain.crosschain.read().unwrap();
+        let res = binding.get_request(params.src_chain_id, params.src_nonce);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!({"error":format!("{:?}",res.err()),"id":self.id}));
+        }
+        let request = res.unwrap();
+        let history: Vec<Value> = request
+            .status_history
+            .iter()
+            .map(|(status, block_number)| json!({"status": status, "block_number": block_number}))
+            .collect();
+        return HttpResponse::Ok().json(json!({
+            "data": {
+                "status": request.get_status(),
+                "history": history,
+                "src_tx_hash": request.src_tx_hash,
+                "dst_tx_hash": request.dst_tx_hash,
+                "ack_tx_hash": request.ack_tx_hash,
+            }
+      ,"id":self.id }));
+    }
+
+    // relayers backfill missing nonces, requests after them are waiting for execution
+    pub fn get_inbound_nonce_gaps(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetInboundNonceGapsParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetInboundNonceGapsParams = res.unwrap();
+        let binding = digichain.crosschain.read().unwrap();
+        let configs = binding.get_contracts_config(vec![params.src_chain_id.clone()]);
+        let res = configs.get(&params.src_chain_id);
+        if res.is_none() {
+            return HttpResponse::BadRequest().json(json!({"error":"contract config not found","id":self.id}));
+        }
+        return HttpResponse::Ok().json(json!({
+            "data": {
+                "last_processed_nonce": res.unwrap().last_proccessed_nonce,
+                "missing_nonces": binding.get_inbound_nonce_gaps(params.src_chain_id.clone()),
+                "pending_nonces": binding.get_pending_inbound_nonces(params.src_chain_id),
+            }
+      ,"id":self.id }));
+    }
+
+    // proof of batched withdraw against merkle root signed in its batch
+    pub fn get_withdraw_batch_proof(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetWithdrawBatchProofParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetWithdrawBatchProofParams = res.unwrap();
+        let binding = digichain.crosschain.read().unwrap();
+        let res = binding.get_request(digichain.chain_id.clone(), params.src_nonce);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!({"error":"request not found","id":self.id}));
+        }
+        let request = res.unwrap();
+        if request.batch_nonce.is_none() {
+            return HttpResponse::BadRequest().json(json!({"error":"withdraw not batched yet","id":self.id}));
+        }
+        let batch_nonce = request.batch_nonce.unwrap();
+        let res = binding.get_request(digichain.chain_id.clone(), batch_nonce);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!({"error":"batch not found","id":self.id}));
+        }
+        let batch = res.unwrap();
+        let dst_chain_id = batch.dst_chain_id.clone().unwrap_or_default();
+        let res = binding.get_batch_leaves(dst_chain_id.clone(), &batch.batch_entries);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!({"error":format!("{:?}",res.err()),"id":self.id}));
+        }
+        let leaves = res.unwrap();
+        let idx = batch.batch_entries.iter().position(|src_nonce| *src_nonce == params.src_nonce);
+        if idx.is_none() {
+            return HttpResponse::BadRequest().json(json!({"error":"withdraw not in batch","id":self.id}));
+        }
+        let idx = idx.unwrap();
+        let res = get_merkle_proof(&leaves, idx);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!({"error":format!("{:?}",res.err()),"id":self.id}));
+        }
+        return HttpResponse::Ok().json(json!({
+            "data": {
+                "batch_nonce": batch_nonce,
+                "dst_chain_id": dst_chain_id,
+                "root": H256::from(get_merkle_root(&leaves)),
+                "leaf": H256::from(leaves[idx]),
+                "proof": res.unwrap().into_iter().map(H256::from).collect::<Vec<H256>>(),
+                "payload": request.payload,
+            }
+      ,"id":self.id }));
+    }
+
+    // latest src chain header receipt proofs can be verified against
+    pub fn get_light_client_head(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetLightClientHeadParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetLightClientHeadParams = res.unwrap();
+        let header = digichain.crosschain.read().unwrap().get_latest_header(params.chain_id);
+        if header.is_none() {
+            return HttpResponse::BadRequest().json(json!({"error":"no header tracked","id":self.id}));
+        }
+        return HttpResponse::Ok().json(json!({"data": header.unwrap(),"id":self.id }));
+    }
+
+    // eip-712 typed data of unsigned tx, wallet signs it with eth_signTypedData_v4
+    pub fn get_typed_data(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetTypedDataParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetTypedDataParams = res.unwrap();
+        if params.transaction.chain_id != digichain.chain_id {
+            return HttpResponse::BadRequest().json(json!({"error":"tx not for this chain","id":self.id}));
+        }
+        let res = get_tx_typed_data(&params.transaction);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!({"error":format!("{:?}",res.err()),"id":self.id}));
+        }
+        let typed_data = res.unwrap();
+        let digest = get_typed_data_digest(&typed_data).ok();
+        return HttpResponse::Ok().json(json!({"data": {"typed_data": get_wallet_typed_data(&typed_data), "digest": digest},"id":self.id }));
+    }
+
+    // last checkpoint anchored on external chain, latest checkpoint signed by quorum
+    pub fn get_last_anchor(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let crosschain = digichain.crosschain.read().unwrap();
+        let anchor = crosschain.get_last_anchor();
+        let latest_checkpoint = crosschain
+            .get_latest_checkpoint()
+            .map(|checkpoint| checkpoint.block_number);
+        return HttpResponse::Ok().json(json!({"data": {"anchor": anchor, "latest_checkpoint": latest_checkpoint},"id":self.id }));
+    }
+
+    // per chain: gateway liquidity must cover what is burned towards it,
+    // per token: supply minted here must be backed by funds still locked on chains
+    pub fn get_bridge_reserves(
+        self,
+        digichain: MutexGuard<'_, DigiChain>,
+        request_body: Params,
+    ) -> HttpResponse {
+        let res: Result<GetBridgeReservesParams , serde_json::Error> = serde_json::from_value(request_body.params);
+        if res.is_err() {
+            return HttpResponse::BadRequest().json(json!(format!("{:?}",res.err()))).into();
+        }
+        let params: GetBridgeReservesParams = res.unwrap();
+        let token_list = digichain.token_list.read().unwrap();
+        let crosschain = digichain.crosschain.read().unwrap();
+        let mut token_ids = params.token_ids.clone();
+        if token_ids.len() == 0usize {
+            token_ids = token_list.keys().cloned().collect();
+        }
+        token_ids.sort();
+        let mut res: Vec<Value> = Vec::new();
+        for token_id in token_ids {
+            let token = token_list.get(&token_id);
+            if token.is_none() {
+                continue;
+            }
+            let token = token.unwrap();
+            let mut chain_ids: Vec<String> = token.chain_token_mapping.keys().cloned().collect();
+            chain_ids.sort();
+            let mut chains: Vec<Value> = Vec::new();
+            let mut backing = Uint128::zero();
+            let mut minted = Uint128::zero();
+            let mut burned = Uint128::zero();
+            for chain_id in chain_ids {
+                let reserve = crosschain.get_reserve(chain_id.clone(), token_id.clone());
+                backing = backing + reserve.get_liquidity();
+                minted = minted + reserve.minted;
+                burned = burned + reserve.burned;
+                if params.chain_id.is_some() && params.chain_id != Some(chain_id.clone()) {
+                    continue;
+                }
+                chains.push(json!({
+                    "chain_id": chain_id,
+                    "token_address": token.chain_token_mapping.get(&chain_id),
+                    "locked": reserve.locked,
+                    "minted": reserve.minted,
+                    "burned": reserve.burned,
+                    "withdrawn": reserve.withdrawn,
+                    "liquidity": reserve.get_liquidity(),
+                    "pending_withdraw": reserve.get_pending_withdraw(),
+                    "deficit": reserve.get_deficit(),
+                    "is_deficit": reserve.get_deficit() > Uint128::zero(),
+                }));
+            }
+            let outstanding = minted.saturating_sub(burned);
+            res.push(json!({
+                "token_id": token_id,
+                "symbol": token.symbol,
+                "chains": chains,
+                "backing": backing,
+                "outstanding": outstanding,
+                "deficit": outstanding.saturating_sub(backing),
+                "is_deficit": outstanding > backing,
+            }));
+        }
+        return HttpResponse::Ok().json(json!({
+            "data": res
+      ,"id":self.id }));
+    }
 
     pub fn airdrop(
         self,
@@ -720,6 +1125,9 @@ pub async fn handler(
         "broadcast_transaction" => {
             JsonRpc::broadcast_transaction(json_rpc, digichain, request_body)
         }
+        "broadcast_proposal" => {
+            JsonRpc::broadcast_proposal(json_rpc, digichain, request_body)
+        }
         "is_broadcasted" => {
             JsonRpc::is_broadcasted(json_rpc, digichain, request_body)
         }
@@ -736,6 +1144,14 @@ pub async fn handler(
         "get_balances" => JsonRpc::get_balances(json_rpc, digichain, request_body),
         "get_transaction" => JsonRpc::get_transaction(json_rpc, digichain, request_body),
         "get_crosschain_requests" => JsonRpc::get_crosschain_requests(json_rpc, digichain, request_body),
+        "query_crosschain_requests" => JsonRpc::query_crosschain_requests(json_rpc, digichain, request_body),
+        "get_bridge_reserves" => JsonRpc::get_bridge_reserves(json_rpc, digichain, request_body),
+        "get_inbound_nonce_gaps" => JsonRpc::get_inbound_nonce_gaps(json_rpc, digichain, request_body),
+        "get_light_client_head" => JsonRpc::get_light_client_head(json_rpc, digichain, request_body),
+        "get_typed_data" => JsonRpc::get_typed_data(json_rpc, digichain, request_body),
+        "get_last_anchor" => JsonRpc::get_last_anchor(json_rpc, digichain, request_body),
+        "get_withdraw_batch_proof" => JsonRpc::get_withdraw_batch_proof(json_rpc, digichain, request_body),
+        "get_crosschain_request_status" => JsonRpc::get_crosschain_request_status(json_rpc, digichain, request_body),
         "get_optimal_path" => JsonRpc::get_optimal_path(json_rpc, digichain, request_body),
 
         "get_transactions" => JsonRpc::get_transactions(json_rpc, digichain, request_body),
@@ -1,12 +1,25 @@
 pub mod acccount;
+#[cfg(feature = "anchor")]
+pub mod anchor;
 pub mod block;
+#[cfg(feature = "bls")]
+pub mod bls;
+pub mod chain_adapter;
+pub mod checkpoint;
 pub mod constants;
 pub mod crosschain;
 pub mod digichain;
 pub mod digicoin;
+pub mod eip712;
+#[cfg(feature = "executor")]
+pub mod executor;
 pub mod json_rpc;
+pub mod light_client;
 pub mod mempool;
+pub mod message;
 pub mod proposal;
+#[cfg(feature = "relayer")]
+pub mod relayer;
 pub mod token;
 pub mod transaction;
 pub mod types;
@@ -0,0 +1,737 @@
+use crate::{
+    constants::LockedFundEventSignature,
+    crosschain::CrossChain,
+    types::{Address, HexString, TxCrossChainRequestParams},
+    utils::{address_to_str, uint_to_u128},
+};
+use cosmwasm_std::Uint128;
+use ethers::{
+    types::H256,
+    utils::{
+        keccak256,
+        rlp::{self, Rlp},
+    },
+};
+use router_wasm_bindings::ethabi::{decode, ParamType};
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+
+// src chain block header tracked by light client
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BlockHeader {
+    pub number: u64,
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub receipts_root: H256,
+}
+
+// proof that receipt of src chain tx emitted the event of inbound request
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReceiptProof {
+    pub header: HexString,     // rlp encoded block header
+    pub proof: Vec<HexString>, // rlp encoded trie nodes from receipts root to receipt
+    pub receipt_index: u64,    // index of tx in block, key in receipts trie
+    pub log_index: u64,        // index of log in receipt
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiptLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+enum NodeRef {
+    Hash(H256),
+    Inline(Vec<u8>),
+}
+
+pub fn decode_header(header: &Vec<u8>) -> Result<BlockHeader, Box<dyn StdError>> {
+    let rlp = Rlp::new(header);
+    let res = rlp.item_count();
+    if res.is_err() || res.unwrap() < 9usize {
+        return Err(format!("invalid header rlp").into());
+    }
+    let parent_hash = get_hash_at(&rlp, 0usize)?;
+    let receipts_root = get_hash_at(&rlp, 5usize)?;
+    let res = rlp.val_at::<u64>(8usize);
+    if res.is_err() {
+        return Err(format!("header number: {:?}", res.err()).into());
+    }
+    Ok(BlockHeader {
+        number: res.unwrap(),
+        hash: H256::from(keccak256(header)),
+        parent_hash,
+        receipts_root,
+    })
+}
+
+// walks proof from root along key, returns value stored at key
+pub fn verify_trie_proof(
+    root: H256,
+    key: &Vec<u8>,
+    proof: &Vec<Vec<u8>>,
+) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let nibbles = key
+        .iter()
+        .flat_map(|byte| vec![byte >> 4, byte & 0x0f])
+        .collect::<Vec<u8>>();
+    let mut nibble_idx = 0usize;
+    let mut proof_idx = 0usize;
+    let mut next = NodeRef::Hash(root);
+    loop {
+        let node = match next {
+            NodeRef::Hash(hash) => {
+                let res = proof.get(proof_idx);
+                if res.is_none() {
+                    return Err(format!("proof ended before value").into());
+                }
+                proof_idx = proof_idx + 1usize;
+                let node = res.unwrap();
+                if H256::from(keccak256(node)) != hash {
+                    return Err(format!("trie node {} hash mismatch", proof_idx - 1usize).into());
+                }
+                node.clone()
+            }
+            NodeRef::Inline(node) => node,
+        };
+        let rlp = Rlp::new(&node);
+        let res = rlp.item_count();
+        if res.is_err() {
+            return Err(format!("trie node: {:?}", res.err()).into());
+        }
+        match res.unwrap() {
+            // branch node
+            17usize => {
+                if nibble_idx == nibbles.len() {
+                    return get_data_at(&rlp, 16usize);
+                }
+                let res = rlp.at(nibbles[nibble_idx] as usize);
+                if res.is_err() {
+                    return Err(format!("branch node: {:?}", res.err()).into());
+                }
+                nibble_idx = nibble_idx + 1usize;
+                next = get_node_ref(&res.unwrap())?;
+            }
+            // extension or leaf node, path is hex prefix encoded
+            2usize => {
+                let path = get_data_at(&rlp, 0usize)?;
+                if path.len() == 0usize {
+                    return Err(format!("empty path in trie node").into());
+                }
+                let is_leaf = path[0] >> 4 >= 2u8;
+                let mut path_nibbles = path[1..]
+                    .iter()
+                    .flat_map(|byte| vec![byte >> 4, byte & 0x0f])
+                    .collect::<Vec<u8>>();
+                if (path[0] >> 4) % 2u8 == 1u8 {
+                    path_nibbles.insert(0usize, path[0] & 0x0f);
+                }
+                if !nibbles[nibble_idx..].starts_with(&path_nibbles) {
+                    return Err(format!("key not in trie").into());
+                }
+                nibble_idx = nibble_idx + path_nibbles.len();
+                if is_leaf {
+                    if nibble_idx != nibbles.len() {
+                        return Err(format!("key not in trie").into());
+                    }
+                    return get_data_at(&rlp, 1usize);
+                }
+                let res = rlp.at(1usize);
+                if res.is_err() {
+                    return Err(format!("extension node: {:?}", res.err()).into());
+                }
+                next = get_node_ref(&res.unwrap())?;
+            }
+            _ => return Err(format!("invalid trie node").into()),
+        }
+    }
+}
+
+// log at log_index of rlp encoded receipt, typed receipts are prefixed with their type
+pub fn decode_receipt_log(
+    receipt: &Vec<u8>,
+    log_index: u64,
+) -> Result<ReceiptLog, Box<dyn StdError>> {
+    if receipt.len() == 0usize {
+        return Err(format!("empty receipt").into());
+    }
+    let mut receipt = receipt.as_slice();
+    if receipt[0] <= 0x7f {
+        receipt = &receipt[1..];
+    }
+    let rlp = Rlp::new(receipt);
+    let res = rlp.at(3usize);
+    if res.is_err() {
+        return Err(format!("receipt logs: {:?}", res.err()).into());
+    }
+    let res = res.unwrap().at(log_index as usize);
+    if res.is_err() {
+        return Err(format!("log {} not in receipt", log_index).into());
+    }
+    let log = res.unwrap();
+    let address = get_data_at(&log, 0usize)?;
+    if address.len() != 20usize {
+        return Err(format!("invalid log address").into());
+    }
+    let res = log.at(1usize);
+    if res.is_err() {
+        return Err(format!("log topics: {:?}", res.err()).into());
+    }
+    let topics_rlp = res.unwrap();
+    let mut topics: Vec<H256> = Vec::new();
+    for idx in 0..topics_rlp.item_count().unwrap_or(0usize) {
+        topics.push(get_hash_at(&topics_rlp, idx)?);
+    }
+    Ok(ReceiptLog {
+        address: Address::from_slice(&address),
+        topics,
+        data: get_data_at(&log, 2usize)?,
+    })
+}
+
+// proof must be for header tracked by light client and its log must be LockedFund event of request
+pub fn verify_locked_fund_proof(
+    crosschain: &CrossChain,
+    params: &TxCrossChainRequestParams,
+    proof: &ReceiptProof,
+) -> Result<bool, Box<dyn StdError>> {
+    let res = proof.header.to_vec();
+    if res.is_err() {
+        return Err(format!("header: {:?}", res.err()).into());
+    }
+    let header = decode_header(&res.unwrap())?;
+    if header.number != params.src_block_number {
+        return Err(format!(
+            "header {} is not of request block {}",
+            header.number, params.src_block_number
+        )
+        .into());
+    }
+    let res = crosschain.get_header(params.src_chain_id.clone(), header.number);
+    if res.is_none() || res.unwrap().hash != header.hash {
+        return Err(format!("header {} not tracked by light client", header.number).into());
+    }
+
+    let mut nodes: Vec<Vec<u8>> = Vec::new();
+    for node in proof.proof.iter() {
+        let res = node.to_vec();
+        if res.is_err() {
+            return Err(format!("trie node: {:?}", res.err()).into());
+        }
+        nodes.push(res.unwrap());
+    }
+    let key = rlp::encode(&proof.receipt_index).to_vec();
+    let receipt = verify_trie_proof(header.receipts_root, &key, &nodes)?;
+    let log = decode_receipt_log(&receipt, proof.log_index)?;
+
+    if log.address != params.src_contract {
+        return Err(format!("log not emitted by {}", address_to_str(params.src_contract)).into());
+    }
+    if log.topics.first() != Some(&H256::from(keccak256(LockedFundEventSignature))) {
+        return Err(format!("log is not LockedFund event").into());
+    }
+    let res = decode(
+        &[
+            ParamType::String,                                //dst_chain_id
+            ParamType::Address,                               //depositor
+            ParamType::Address,                               //recipient
+            ParamType::Array(Box::new(ParamType::Address)),   //vec<tokens>
+            ParamType::Array(Box::new(ParamType::Uint(256))), //vec<amounts>
+            ParamType::Uint(256),                             //nonce
+        ],
+        &log.data,
+    );
+    if res.is_err() {
+        return Err(format!("LockedFund data: {:?}", res.err()).into());
+    }
+    let decoded_data = res.unwrap();
+    let dst_chain_id = decoded_data[0].clone().into_string();
+    let depositor = decoded_data[1]
+        .clone()
+        .into_address()
+        .map(|address| Address::from_slice(address.as_bytes()));
+    let recipient = decoded_data[2]
+        .clone()
+        .into_address()
+        .map(|address| Address::from_slice(address.as_bytes()));
+    let tokens = decoded_data[3].clone().into_array().map(|tokens| {
+        tokens
+            .into_iter()
+            .filter_map(|token| token.into_address())
+            .map(|address| Address::from_slice(address.as_bytes()))
+            .collect::<Vec<Address>>()
+    });
+    // event data is supplied by relayer, out of range values are rejected
+    let mut amounts: Option<Vec<Uint128>> = None;
+    if let Some(tokens) = decoded_data[4].clone().into_array() {
+        let mut values: Vec<Uint128> = Vec::new();
+        for token in tokens {
+            let res = token.into_uint();
+            if res.is_none() {
+                continue;
+            }
+            values.push(uint_to_u128(res.unwrap(), "LockedFund amount")?);
+        }
+        amounts = Some(values);
+    }
+    let mut nonce: Option<Uint128> = None;
+    if let Some(value) = decoded_data[5].clone().into_uint() {
+        nonce = Some(uint_to_u128(value, "LockedFund nonce")?);
+    }
+    if dst_chain_id != Some(params.dst_chain_id.clone())
+        || depositor != Some(params.depositor)
+        || recipient != Some(params.recipient)
+        || tokens != Some(params.tokens.clone())
+        || amounts != Some(params.amounts.clone())
+        || nonce != Some(params.src_nonce)
+    {
+        return Err(format!("LockedFund event doesn't match request").into());
+    }
+    Ok(true)
+}
+
+fn get_data_at(rlp: &Rlp, idx: usize) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let res = rlp.at(idx);
+    if res.is_err() {
+        return Err(format!("rlp item {}: {:?}", idx, res.err()).into());
+    }
+    let res = res.unwrap().data().map(|data| data.to_vec());
+    if res.is_err() {
+        return Err(format!("rlp item {}: {:?}", idx, res.err()).into());
+    }
+    Ok(res.unwrap())
+}
+
+fn get_hash_at(rlp: &Rlp, idx: usize) -> Result<H256, Box<dyn StdError>> {
+    let data = get_data_at(rlp, idx)?;
+    if data.len() != 32usize {
+        return Err(format!("rlp item {} is not a hash", idx).into());
+    }
+    Ok(H256::from_slice(&data))
+}
+
+// child is referenced by hash, nodes shorter than 32 bytes are inlined
+fn get_node_ref(child: &Rlp) -> Result<NodeRef, Box<dyn StdError>> {
+    if child.is_list() {
+        return Ok(NodeRef::Inline(child.as_raw().to_vec()));
+    }
+    let res = child.data();
+    if res.is_err() {
+        return Err(format!("trie child: {:?}", res.err()).into());
+    }
+    let data = res.unwrap();
+    if data.len() != 32usize {
+        return Err(format!("key not in trie").into());
+    }
+    Ok(NodeRef::Hash(H256::from_slice(data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ChainTypeEvm;
+    use ethers::utils::{hex, rlp::RlpStream};
+    use router_wasm_bindings::ethabi::{
+        encode, ethereum_types::U256, Address as EthRouterAddress, Token,
+    };
+    use std::str::FromStr;
+
+    const RECEIPT_INDEX: u64 = 5u64;
+    const LOG_INDEX: u64 = 1u64;
+    const BLOCK_NUMBER: u64 = 100u64;
+
+    // nibbles of key and value stored at it
+    type TrieEntry = (Vec<u8>, Vec<u8>);
+
+    fn to_nibbles(key: &[u8]) -> Vec<u8> {
+        key.iter()
+            .flat_map(|byte| vec![byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2u8 } else { 0u8 };
+        let mut encoded = Vec::new();
+        let mut rest = nibbles;
+        if nibbles.len() % 2usize == 1usize {
+            encoded.push(((flag + 1u8) << 4) | nibbles[0]);
+            rest = &nibbles[1..];
+        } else {
+            encoded.push(flag << 4);
+        }
+        for pair in rest.chunks(2usize) {
+            encoded.push((pair[0] << 4) | pair[1]);
+        }
+        encoded
+    }
+
+    // nodes shorter than 32 bytes are inlined, hashed nodes on path of key go in proof
+    fn append_child(
+        stream: &mut RlpStream,
+        node: Vec<u8>,
+        on_path: bool,
+        proof: &mut Vec<Vec<u8>>,
+    ) {
+        if node.len() < 32usize {
+            stream.append_raw(&node, 1usize);
+            return;
+        }
+        stream.append(&keccak256(&node).to_vec());
+        if on_path {
+            proof.push(node);
+        }
+    }
+
+    // trie node of entries below depth, proof nodes are collected from leaf up
+    fn encode_node(
+        entries: &[TrieEntry],
+        depth: usize,
+        key: Option<&[u8]>,
+        proof: &mut Vec<Vec<u8>>,
+    ) -> Vec<u8> {
+        if entries.len() == 1usize && entries[0].0.len() > depth {
+            let mut stream = RlpStream::new_list(2usize);
+            stream.append(&hex_prefix(&entries[0].0[depth..], true));
+            stream.append(&entries[0].1);
+            return stream.out().to_vec();
+        }
+        let mut prefix_len = 0usize;
+        loop {
+            let idx = depth + prefix_len;
+            if entries.iter().any(|entry| entry.0.len() <= idx)
+                || entries
+                    .iter()
+                    .any(|entry| entry.0[idx] != entries[0].0[idx])
+            {
+                break;
+            }
+            prefix_len = prefix_len + 1usize;
+        }
+        if prefix_len > 0usize {
+            let child = encode_node(entries, depth + prefix_len, key, proof);
+            let mut stream = RlpStream::new_list(2usize);
+            stream.append(&hex_prefix(&entries[0].0[depth..depth + prefix_len], false));
+            append_child(&mut stream, child, key.is_some(), proof);
+            return stream.out().to_vec();
+        }
+        let mut stream = RlpStream::new_list(17usize);
+        for nibble in 0u8..16u8 {
+            let group = entries
+                .iter()
+                .filter(|entry| entry.0.len() > depth && entry.0[depth] == nibble)
+                .cloned()
+                .collect::<Vec<TrieEntry>>();
+            if group.len() == 0usize {
+                stream.append_empty_data();
+                continue;
+            }
+            let child_key = key.filter(|key| key.len() > depth && key[depth] == nibble);
+            let child = encode_node(&group, depth + 1usize, child_key, proof);
+            append_child(&mut stream, child, child_key.is_some(), proof);
+        }
+        match entries.iter().find(|entry| entry.0.len() == depth) {
+            Some(entry) => stream.append(&entry.1),
+            None => stream.append_empty_data(),
+        };
+        stream.out().to_vec()
+    }
+
+    // receipts trie keyed by rlp of index, returns root and proof of receipt at index
+    fn get_receipts_trie(receipts: &Vec<Vec<u8>>, index: u64) -> (H256, Vec<Vec<u8>>) {
+        let entries = receipts
+            .iter()
+            .enumerate()
+            .map(|(idx, receipt)| (to_nibbles(&rlp::encode(&(idx as u64))), receipt.clone()))
+            .collect::<Vec<TrieEntry>>();
+        let key = to_nibbles(&rlp::encode(&index));
+        let mut proof = Vec::new();
+        let root = encode_node(&entries, 0usize, Some(&key), &mut proof);
+        let root_hash = H256::from(keccak256(&root));
+        proof.push(root);
+        proof.reverse();
+        (root_hash, proof)
+    }
+
+    fn get_gateway() -> Address {
+        Address::from_low_u64_be(2u64)
+    }
+
+    fn get_params() -> TxCrossChainRequestParams {
+        TxCrossChainRequestParams {
+            src_chain_id: "1".to_string(),
+            dst_chain_id: "digichain".to_string(),
+            src_contract: get_gateway(),
+            recipient: Address::from_low_u64_be(5u64),
+            depositor: Address::from_low_u64_be(4u64),
+            tokens: vec![Address::from_low_u64_be(3u64)],
+            amounts: vec![Uint128::from(100u128)],
+            src_nonce: Uint128::from(7u128),
+            src_block_number: BLOCK_NUMBER,
+            src_tx_hash: String::new(),
+        }
+    }
+
+    fn address_token(address: Address) -> Token {
+        Token::Address(EthRouterAddress::from_slice(address.as_bytes()))
+    }
+
+    // eip-1559 receipt with transfer log before LockedFund event of params
+    fn get_receipt(params: &TxCrossChainRequestParams) -> Vec<u8> {
+        let event_data = encode(&[
+            Token::String(params.dst_chain_id.clone()),
+            address_token(params.depositor),
+            address_token(params.recipient),
+            Token::Array(params.tokens.iter().cloned().map(address_token).collect()),
+            Token::Array(
+                params
+                    .amounts
+                    .iter()
+                    .map(|amount| Token::Uint(U256::from(amount.u128())))
+                    .collect(),
+            ),
+            Token::Uint(U256::from(params.src_nonce.u128())),
+        ]);
+        let logs = vec![
+            (
+                params.tokens[0],
+                vec![H256::from(keccak256("Transfer(address,address,uint256)"))],
+                vec![0u8; 32],
+            ),
+            (
+                params.src_contract,
+                vec![H256::from(keccak256(LockedFundEventSignature))],
+                event_data,
+            ),
+        ];
+        let mut stream = RlpStream::new_list(4usize);
+        stream.append(&1u64);
+        stream.append(&84000u64);
+        stream.append(&vec![0u8; 256]);
+        stream.begin_list(logs.len());
+        for (address, topics, data) in logs.iter() {
+            stream.begin_list(3usize);
+            stream.append(&address.as_bytes().to_vec());
+            stream.begin_list(topics.len());
+            for topic in topics.iter() {
+                stream.append(&topic.as_bytes().to_vec());
+            }
+            stream.append(data);
+        }
+        let mut receipt = vec![2u8];
+        receipt.extend(stream.out().to_vec());
+        receipt
+    }
+
+    // london header, only parent hash, receipts root and number matter here
+    fn get_header(number: u64, receipts_root: H256) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(16usize);
+        stream.append(&H256::zero().as_bytes().to_vec());
+        stream.append(&H256::zero().as_bytes().to_vec());
+        stream.append(&Address::zero().as_bytes().to_vec());
+        stream.append(&H256::zero().as_bytes().to_vec());
+        stream.append(&H256::zero().as_bytes().to_vec());
+        stream.append(&receipts_root.as_bytes().to_vec());
+        stream.append(&vec![0u8; 256]);
+        stream.append(&0u64);
+        stream.append(&number);
+        stream.append(&30000000u64);
+        stream.append(&84000u64);
+        stream.append(&1700000000u64);
+        stream.append(&Vec::<u8>::new());
+        stream.append(&H256::zero().as_bytes().to_vec());
+        stream.append(&vec![0u8; 8]);
+        stream.append(&7u64);
+        stream.out().to_vec()
+    }
+
+    // 20 receipts so proof goes through branch nodes, header is tracked by light client
+    fn get_proof() -> (CrossChain, ReceiptProof, Vec<Vec<u8>>) {
+        let params = get_params();
+        let receipts = (0..20u64)
+            .map(|idx| {
+                if idx == RECEIPT_INDEX {
+                    return get_receipt(&params);
+                }
+                let mut other = params.clone();
+                other.src_nonce = Uint128::from(idx);
+                get_receipt(&other)
+            })
+            .collect::<Vec<Vec<u8>>>();
+        let (receipts_root, proof) = get_receipts_trie(&receipts, RECEIPT_INDEX);
+        let header = get_header(BLOCK_NUMBER, receipts_root);
+        let mut crosschain = CrossChain::default();
+        crosschain
+            .add_contract_config(
+                "1".to_string(),
+                format!("{:?}", get_gateway()),
+                0u64,
+                ChainTypeEvm,
+            )
+            .unwrap();
+        crosschain
+            .add_header("1".to_string(), decode_header(&header).unwrap())
+            .unwrap();
+        let receipt_proof = ReceiptProof {
+            header: HexString::from_vec(header),
+            proof: proof.iter().cloned().map(HexString::from_vec).collect(),
+            receipt_index: RECEIPT_INDEX,
+            log_index: LOG_INDEX,
+        };
+        (crosschain, receipt_proof, receipts)
+    }
+
+    #[test]
+    fn decodes_mainnet_header() {
+        // ethereum mainnet block 436
+        let mut stream = RlpStream::new_list(15usize);
+        for hash in [
+            "e99e022112df268087ea7eafaf4790497fd21dbeeb6bd7a1721df161a6657a54",
+            "1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+        ] {
+            stream.append(&hex::decode(hash).unwrap());
+        }
+        stream.append(&hex::decode("bb7b8287f3f0a933474a79eae42cbca977791171").unwrap());
+        for hash in [
+            "ddc8b0234c2e0cad087c8b389aa7ef01f7d79b2570bccb77ce48648aa61c904d",
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        ] {
+            stream.append(&hex::decode(hash).unwrap());
+        }
+        stream.append(&vec![0u8; 256]);
+        stream.append(&0x4ea3f27bcu64);
+        stream.append(&436u64);
+        stream.append(&0x1388u64);
+        stream.append(&0u64);
+        stream.append(&0x55ba467cu64);
+        stream.append(
+            &hex::decode("476574682f4c5649562f76312e302e302f6c696e75782f676f312e342e32").unwrap(),
+        );
+        stream.append(
+            &hex::decode("4fffe9ae21f1c9e15207b1f472d5bbdd68c9595d461666602f2be20daf5e7843")
+                .unwrap(),
+        );
+        stream.append(&hex::decode("689056015818adbe").unwrap());
+
+        let header = decode_header(&stream.out().to_vec()).unwrap();
+        assert_eq!(header.number, 436u64);
+        assert_eq!(
+            header.hash,
+            H256::from_str("0xdc0818cf78f21a8e70579cb46a43643f78291264dda342ae31049421c82d21ae")
+                .unwrap()
+        );
+        assert_eq!(
+            header.parent_hash,
+            H256::from_str("0xe99e022112df268087ea7eafaf4790497fd21dbeeb6bd7a1721df161a6657a54")
+                .unwrap()
+        );
+        // empty receipts trie
+        assert_eq!(header.receipts_root, H256::from(keccak256(rlp::NULL_RLP)));
+    }
+
+    #[test]
+    fn builds_reference_trie() {
+        // puppy vector of ethereum/tests trie tests, checks trie built for proofs below
+        let mut entries = vec![
+            (to_nibbles(b"do"), b"verb".to_vec()),
+            (to_nibbles(b"horse"), b"stallion".to_vec()),
+            (to_nibbles(b"doge"), b"coin".to_vec()),
+            (to_nibbles(b"dog"), b"puppy".to_vec()),
+        ];
+        entries.sort();
+        let key = to_nibbles(b"dog");
+        let mut proof = Vec::new();
+        let root = encode_node(&entries, 0usize, Some(&key), &mut proof);
+        assert_eq!(
+            H256::from(keccak256(&root)),
+            H256::from_str("0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84")
+                .unwrap()
+        );
+        proof.push(root);
+        proof.reverse();
+        assert_eq!(
+            verify_trie_proof(H256::from(keccak256(&proof[0])), &b"dog".to_vec(), &proof).unwrap(),
+            b"puppy".to_vec()
+        );
+    }
+
+    #[test]
+    fn verifies_locked_fund_proof() {
+        let (crosschain, proof, receipts) = get_proof();
+        let nodes = proof
+            .proof
+            .iter()
+            .map(|node| node.to_vec().unwrap())
+            .collect::<Vec<Vec<u8>>>();
+        let receipts_root = decode_header(&proof.header.to_vec().unwrap())
+            .unwrap()
+            .receipts_root;
+        // proof goes through root branch, branch of 0x0 keys and leaf
+        assert_eq!(nodes.len(), 3usize);
+        assert_eq!(
+            verify_trie_proof(receipts_root, &rlp::encode(&RECEIPT_INDEX).to_vec(), &nodes)
+                .unwrap(),
+            receipts[RECEIPT_INDEX as usize]
+        );
+        assert!(verify_locked_fund_proof(&crosschain, &get_params(), &proof).unwrap());
+    }
+
+    #[test]
+    fn rejects_tampered_trie_node() {
+        let (crosschain, mut proof, _) = get_proof();
+        let mut leaf = proof.proof[2].to_vec().unwrap();
+        let last = leaf.len() - 1usize;
+        leaf[last] ^= 1u8;
+        proof.proof[2] = HexString::from_vec(leaf);
+        let res = verify_locked_fund_proof(&crosschain, &get_params(), &proof);
+        assert!(format!("{:?}", res.err().unwrap()).contains("hash mismatch"));
+    }
+
+    #[test]
+    fn rejects_wrong_receipts_root() {
+        let (crosschain, mut proof, _) = get_proof();
+        // same block with other receipts root isn't tracked header
+        let header = get_header(BLOCK_NUMBER, H256::from_low_u64_be(1u64));
+        proof.header = HexString::from_vec(header.clone());
+        let res = verify_locked_fund_proof(&crosschain, &get_params(), &proof);
+        assert!(format!("{:?}", res.err().unwrap()).contains("not tracked"));
+        // proof doesn't lead to other root either
+        let nodes = proof
+            .proof
+            .iter()
+            .map(|node| node.to_vec().unwrap())
+            .collect::<Vec<Vec<u8>>>();
+        assert!(verify_trie_proof(
+            H256::from_low_u64_be(1u64),
+            &rlp::encode(&RECEIPT_INDEX).to_vec(),
+            &nodes
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_log_and_receipt_index() {
+        let (crosschain, mut proof, _) = get_proof();
+        // transfer log of same receipt
+        proof.log_index = 0u64;
+        let res = verify_locked_fund_proof(&crosschain, &get_params(), &proof);
+        assert!(res.is_err());
+        proof.log_index = 2u64;
+        let res = verify_locked_fund_proof(&crosschain, &get_params(), &proof);
+        assert!(format!("{:?}", res.err().unwrap()).contains("not in receipt"));
+        // proof is of other key
+        proof.log_index = LOG_INDEX;
+        proof.receipt_index = RECEIPT_INDEX + 1u64;
+        assert!(verify_locked_fund_proof(&crosschain, &get_params(), &proof).is_err());
+    }
+
+    #[test]
+    fn rejects_event_of_other_request() {
+        let (crosschain, proof, _) = get_proof();
+        let mut params = get_params();
+        params.amounts = vec![Uint128::from(101u128)];
+        let res = verify_locked_fund_proof(&crosschain, &params, &proof);
+        assert!(format!("{:?}", res.err().unwrap()).contains("doesn't match"));
+    }
+}
@@ -30,7 +30,23 @@ async fn main() -> std::io::Result<()> {
     println!("Starting Chain!!");
     let account = Account::new(Address::from_str(&res.unwrap()).unwrap());
     let wallet = Wallet::from_str(&private_key_res.unwrap()).unwrap();
-    let validator = Validator::new(account.clone(), Uint128::from(100u128), wallet);
+    #[allow(unused_mut)]
+    let mut validator = Validator::new(account.clone(), Uint128::from(100u128), wallet);
+    // bls key is not derived from PRIVATE_KEY, BLS_SECRET_KEY=hex of 32 byte secret
+    #[cfg(feature = "bls")]
+    {
+        let bls_secret_key_res = env::var("BLS_SECRET_KEY");
+        if bls_secret_key_res.is_err() {
+            panic!("define BLS_SECRET_KEY in .env file");
+        }
+        let secret_key = digichain::bls::load_secret_key(&digichain::types::HexString::new(
+            bls_secret_key_res.unwrap(),
+        ));
+        if secret_key.is_err() {
+            panic!("invalid BLS_SECRET_KEY: {:?}", secret_key.err());
+        }
+        validator.set_bls_secret_key(secret_key.unwrap());
+    }
     let timestamp = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
@@ -42,6 +58,7 @@ async fn main() -> std::io::Result<()> {
         "".to_string(),
         vec![],
         vec![],
+        "".to_string(),
     );
     let mut accounts = HashMap::new();
     accounts.insert(account.address.clone(), Arc::new(RwLock::new(account)));
@@ -67,6 +84,12 @@ async fn main() -> std::io::Result<()> {
     env_logger::init();
 
     let digichain_arc = Arc::new(Mutex::new(digichain.clone()));
+    #[cfg(feature = "relayer")]
+    start_relayer(digichain.clone());
+    #[cfg(feature = "executor")]
+    start_executor(digichain.clone());
+    #[cfg(feature = "anchor")]
+    start_anchorer(digichain.clone());
     tokio::spawn(async move {
         DigiChain::add_blocks(&mut digichain.clone()).await;
     });
@@ -82,3 +105,118 @@ async fn main() -> std::io::Result<()> {
     .run()
     .await
 }
+
+// RELAYER_RPC_URLS=chain_id=url,chain_id=url
+#[cfg(feature = "relayer")]
+fn get_chain_providers() -> Vec<(String, ethers::providers::Provider<ethers::providers::Http>)> {
+    use ethers::providers::{Http, Provider};
+
+    let res = env::var("RELAYER_RPC_URLS");
+    if res.is_err() {
+        return vec![];
+    }
+    let mut providers = vec![];
+    for entry in res.unwrap().split(',') {
+        let res = entry.split_once('=');
+        if res.is_none() {
+            panic!("invalid RELAYER_RPC_URLS entry {}", entry);
+        }
+        let (chain_id, url) = res.unwrap();
+        let provider = Provider::<Http>::try_from(url.trim());
+        if provider.is_err() {
+            panic!(
+                "invalid rpc url for chain {}: {:?}",
+                chain_id,
+                provider.err()
+            );
+        }
+        providers.push((chain_id.trim().to_string(), provider.unwrap()));
+    }
+    providers
+}
+
+#[cfg(feature = "relayer")]
+fn start_relayer(digichain: DigiChain) {
+    use digichain::relayer::{Relayer, RelayerConfig};
+
+    let providers = get_chain_providers();
+    if providers.len() == 0 {
+        println!("Relayer Disabled || define RELAYER_RPC_URLS in .env file to enable it");
+        return;
+    }
+    let mut relayer = Relayer::new(RelayerConfig::default());
+    for (chain_id, provider) in providers {
+        relayer.add_provider(chain_id, Arc::new(provider));
+    }
+    tokio::spawn(async move {
+        relayer.run(digichain).await;
+    });
+}
+
+// withdraws are signed on dst chain with validator wallet, chain ids need to be evm chain ids
+#[cfg(feature = "executor")]
+fn start_executor(digichain: DigiChain) {
+    use digichain::constants::RelayerPollInterval;
+    use digichain::executor::{EvmGateway, Executor};
+    use ethers::middleware::SignerMiddleware;
+    use ethers_signers::Signer;
+
+    let providers = get_chain_providers();
+    if providers.len() == 0 {
+        println!("Executor Disabled || define RELAYER_RPC_URLS in .env file to enable it");
+        return;
+    }
+    let wallet = digichain.validator.read().unwrap().wallet.clone();
+    let mut executor = Executor::new(RelayerPollInterval);
+    for (chain_id, provider) in providers {
+        let res = chain_id.parse::<u64>();
+        if res.is_err() {
+            println!(
+                "Executor Skipped Chain || ChainId: {}, not an evm chain",
+                chain_id
+            );
+            continue;
+        }
+        let client = SignerMiddleware::new(provider, wallet.clone().with_chain_id(res.unwrap()));
+        executor.add_gateway(chain_id, EvmGateway::new(Arc::new(client)));
+    }
+    tokio::spawn(async move {
+        executor.run(digichain).await;
+    });
+}
+
+// ANCHOR_CHAIN_ID=evm chain id, ANCHOR_RPC_URL=url, anchor contract is taken from contract configs
+#[cfg(feature = "anchor")]
+fn start_anchorer(digichain: DigiChain) {
+    use digichain::anchor::{Anchorer, EvmAnchorClient};
+    use digichain::constants::RelayerPollInterval;
+    use ethers::middleware::SignerMiddleware;
+    use ethers::providers::{Http, Provider};
+    use ethers_signers::Signer;
+
+    let chain_id = env::var("ANCHOR_CHAIN_ID");
+    let url = env::var("ANCHOR_RPC_URL");
+    if chain_id.is_err() || url.is_err() {
+        println!("Anchorer Disabled || define ANCHOR_CHAIN_ID and ANCHOR_RPC_URL in .env file to enable it");
+        return;
+    }
+    let chain_id = chain_id.unwrap();
+    let res = chain_id.parse::<u64>();
+    if res.is_err() {
+        panic!("ANCHOR_CHAIN_ID {} is not an evm chain id", chain_id);
+    }
+    let provider = Provider::<Http>::try_from(url.unwrap().trim());
+    if provider.is_err() {
+        panic!("invalid ANCHOR_RPC_URL: {:?}", provider.err());
+    }
+    let wallet = digichain.validator.read().unwrap().wallet.clone();
+    let client = SignerMiddleware::new(provider.unwrap(), wallet.with_chain_id(res.unwrap()));
+    let mut anchorer = Anchorer::new(
+        chain_id,
+        RelayerPollInterval,
+        EvmAnchorClient::new(Arc::new(client)),
+    );
+    tokio::spawn(async move {
+        anchorer.run(digichain).await;
+    });
+}
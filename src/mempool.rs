@@ -1,4 +1,5 @@
 use crate::block::DigiBlock;
+use crate::constants::{MaxQueuedTxAge, MaxQueuedTxsPerAccount};
 use crate::digichain::DigiChain;
 use crate::proposal::{CrossChainWithdrawMsg, Proposal, ProposalType};
 use crate::transaction::Transaction;
@@ -7,16 +8,19 @@ use cosmwasm_std::Uint128;
 use ethers::types::Signature;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error as StdError;
 use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 
 #[derive(Clone, Debug)]
 pub struct Mempool {
     pub proposals: Arc<RwLock<HashMap<String, Vec<Proposal>>>>,
     pub attested_idx: Arc<RwLock<HashMap<String, usize>>>,
 
-    pub transactions: Vec<Transaction>,
+    pub transactions: Vec<Transaction>, // ready, each account's nonces follow its account nonce
+    pub queued_transactions: HashMap<Address, BTreeMap<Uint128, Transaction>>, // future nonce txs of account, moved to transactions once gap is filled
+    pub queued_at: HashMap<String, u64>, // tx hash -> time tx got queued, dropped once it waits too long
     pub crosschain_request: HashMap<Address, Vec<CrossChainWithdrawMsg>>, // validator -> array of vector to process
 }
 
@@ -27,6 +31,8 @@ impl Mempool {
             attested_idx: Arc::new(RwLock::new(Default::default())),
             proposals: Arc::new(RwLock::new(Default::default())),
             transactions: vec![],
+            queued_transactions: Default::default(),
+            queued_at: Default::default(),
         }
     }
     pub fn get_mempool(&self) -> Mempool {
@@ -53,6 +59,36 @@ impl Mempool {
         );
     }
 
+    // request got reassigned to other validator
+    pub fn move_crosschain_request(
+        &mut self,
+        from: Address,
+        to: Address,
+        src_chain_id: String,
+        src_nonce: Uint128,
+    ) -> bool {
+        let res = self.crosschain_request.get_mut(&from);
+        if res.is_none() {
+            return false;
+        }
+        let ls = res.unwrap();
+        let idx = ls
+            .iter()
+            .position(|r| r.src_chain_id == src_chain_id && r.src_nonce == src_nonce);
+        if idx.is_none() {
+            return false;
+        }
+        let withdraw_request = ls.remove(idx.unwrap());
+        self.add_crosschain_request(to, &withdraw_request);
+        true
+    }
+
+    pub fn remove_crosschain_request(&mut self, src_chain_id: String, src_nonce: Uint128) {
+        for ls in self.crosschain_request.values_mut() {
+            ls.retain(|r| !(r.src_chain_id == src_chain_id && r.src_nonce == src_nonce));
+        }
+    }
+
     pub fn get_crosschain_request_to_execute(
         &self,
         validator: Address,
@@ -75,11 +111,154 @@ impl Mempool {
         proposals
     }
 
-    pub fn add_transaction(&mut self, tx: &Transaction) {
-        // TODO: verify is proposal valid, signature is valid or not
-        let tx_hash = tx.hash.clone();
+    // nonce next tx of account has to carry, account nonce followed by its ready txs
+    pub fn get_pending_nonce(&self, address: Address, account_nonce: Uint128) -> Uint128 {
+        self.transactions
+            .iter()
+            .filter(|tx| tx.from == address && tx.nonce >= account_nonce)
+            .fold(account_nonce, |nonce, tx| {
+                nonce.max(tx.nonce + Uint128::one())
+            })
+    }
+
+    // stale (already executed) and duplicate nonces are rejected
+    pub fn check_tx_nonce(
+        &self,
+        tx: &Transaction,
+        account_nonce: Uint128,
+    ) -> Result<(), Box<dyn StdError>> {
+        if tx.nonce < account_nonce {
+            return Err(format!(
+                "stale nonce {}, account nonce is {}",
+                tx.nonce, account_nonce
+            )
+            .into());
+        }
+        let queue = self.queued_transactions.get(&tx.from);
+        if self
+            .transactions
+            .iter()
+            .any(|ptx| ptx.from == tx.from && ptx.nonce == tx.nonce)
+            || queue.map_or(false, |queue| queue.contains_key(&tx.nonce))
+        {
+            return Err(format!("duplicate nonce {}", tx.nonce).into());
+        }
+        if tx.nonce > self.get_pending_nonce(tx.from, account_nonce)
+            && queue.map_or(0usize, |queue| queue.len()) >= MaxQueuedTxsPerAccount
+        {
+            return Err(format!("too many queued txs of {:?}", tx.from).into());
+        }
+        Ok(())
+    }
+
+    // tx carrying next nonce of account is ready to be selected, future nonce waits in queue
+    // of account until gap is filled, returns false if tx got queued
+    pub fn add_transaction(
+        &mut self,
+        tx: &Transaction,
+        account_nonce: Uint128,
+    ) -> Result<bool, Box<dyn StdError>> {
+        self.check_tx_nonce(tx, account_nonce)?;
+        if tx.nonce > self.get_pending_nonce(tx.from, account_nonce) {
+            self.queue_transaction(tx.clone());
+            return Ok(false);
+        }
         self.transactions.push(tx.clone());
-        println!("Transaction Added ||  Hash: {:?}", tx_hash);
+        println!("Transaction Added ||  Hash: {:?}", tx.hash);
+        self.promote_queued_transactions(tx.from, account_nonce);
+        Ok(true)
+    }
+
+    fn queue_transaction(&mut self, tx: Transaction) {
+        println!(
+            "Transaction Queued ||  Hash: {:?}, Nonce: {}",
+            tx.hash, tx.nonce
+        );
+        self.queued_at.insert(tx.hash.clone(), get_now());
+        self.queued_transactions
+            .entry(tx.from)
+            .or_default()
+            .insert(tx.nonce, tx);
+    }
+
+    // ready txs of account behind nonce gap, e.g. left by invalid tx dropped before them, wait in queue again
+    fn demote_transactions(&mut self, address: Address, account_nonce: Uint128) {
+        let mut nonces = self
+            .transactions
+            .iter()
+            .filter(|tx| tx.from == address)
+            .map(|tx| tx.nonce)
+            .collect::<Vec<Uint128>>();
+        nonces.sort();
+        let mut next_nonce = account_nonce;
+        for nonce in nonces {
+            if nonce == next_nonce {
+                next_nonce = next_nonce + Uint128::one();
+            }
+        }
+        let (demoted, ready): (Vec<Transaction>, Vec<Transaction>) = self
+            .transactions
+            .drain(..)
+            .partition(|tx| tx.from == address && tx.nonce >= next_nonce);
+        self.transactions = ready;
+        for tx in demoted {
+            self.queue_transaction(tx);
+        }
+    }
+
+    // queued txs whose nonce gap isn't filled in time are dropped
+    fn drop_expired_queued_transactions(&mut self) {
+        let now = get_now();
+        let expired: HashSet<String> = self
+            .queued_transactions
+            .values()
+            .flat_map(|queue| queue.values())
+            .filter(|tx| {
+                self.queued_at
+                    .get(&tx.hash)
+                    .map_or(true, |at| now.saturating_sub(*at) >= MaxQueuedTxAge)
+            })
+            .map(|tx| tx.hash.clone())
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+        for queue in self.queued_transactions.values_mut() {
+            queue.retain(|_, tx| !expired.contains(&tx.hash));
+        }
+        self.queued_transactions
+            .retain(|_, queue| !queue.is_empty());
+        for hash in expired {
+            println!("Queued Transaction Expired ||  Hash: {:?}", hash);
+            self.queued_at.remove(&hash);
+        }
+    }
+
+    // moves queued txs of account which now follow its ready txs without gap
+    fn promote_queued_transactions(&mut self, address: Address, account_nonce: Uint128) {
+        let mut next_nonce = self.get_pending_nonce(address, account_nonce);
+        let res = self.queued_transactions.get_mut(&address);
+        if res.is_none() {
+            return;
+        }
+        let queue = res.unwrap();
+        let queued_at = &mut self.queued_at;
+        queue.retain(|nonce, tx| {
+            if *nonce < next_nonce {
+                queued_at.remove(&tx.hash);
+                return false;
+            }
+            true
+        });
+        while let Some(tx) = queue.remove(&next_nonce) {
+            queued_at.remove(&tx.hash);
+            println!("Transaction Added ||  Hash: {:?}", tx.hash);
+            self.transactions.push(tx);
+            next_nonce = next_nonce + Uint128::one();
+        }
+        if queue.is_empty() {
+            self.queued_transactions.remove(&address);
+        }
     }
 
     // broadcast to other validators after signing
@@ -104,32 +283,58 @@ impl Mempool {
     }
 
     pub fn select_txs_randomly(&mut self, digichain: DigiChain) -> Vec<Transaction> {
+        self.drop_expired_queued_transactions();
         if self.transactions.len() == 0usize {
             return vec![];
         }
         let mut rng = rand::thread_rng();
-        let mut indices: Vec<usize> = (0..self.transactions.len()).collect();
-        // indices.shuffle(&mut rng);
         let mut subset_length = rng.gen_range(1..=20);
         if subset_length > self.transactions.len() {
             subset_length = self.transactions.len();
         }
 
-        let selected_transactions: Vec<Transaction> = indices
-            .into_iter()
-            .take(subset_length)
-            .filter_map(|index| {
-                let transaction: Transaction = self.transactions[index].clone();
-                if transaction.is_valid(digichain.clone()) {
-                    Some(transaction)
-                } else {
-                    // If not valid, remove from self.transactions
-                    // and return None (not included in the result)
-                    self.transactions.remove(index);
-                    None
-                }
-            })
-            .collect();
+        // txs of each account are selected in strict nonce order starting from its account nonce,
+        // tx after a gap waits for next block
+        let mut candidates = self.transactions.clone();
+        candidates.sort_by(|a, b| a.nonce.cmp(&b.nonce));
+        let mut next_nonces: HashMap<Address, Uint128> = HashMap::new();
+        // invalid txs (e.g. not signed by sender) and stale nonces are dropped from mempool
+        let mut invalid_hashes: Vec<String> = Vec::new();
+        let mut invalid_senders: Vec<Address> = Vec::new();
+        let mut selected_transactions: Vec<Transaction> = Vec::new();
+        for transaction in candidates {
+            if selected_transactions.len() >= subset_length {
+                break;
+            }
+            if !transaction.is_valid(digichain.clone()) {
+                invalid_hashes.push(transaction.hash);
+                invalid_senders.push(transaction.from);
+                continue;
+            }
+            let next_nonce = *next_nonces.entry(transaction.from).or_insert_with(|| {
+                digichain
+                    .get_account(transaction.from)
+                    .map_or(Uint128::zero(), |account| account.tx_nonce)
+            });
+            if transaction.nonce < next_nonce {
+                invalid_hashes.push(transaction.hash);
+                continue;
+            }
+            if transaction.nonce > next_nonce {
+                continue;
+            }
+            next_nonces.insert(transaction.from, next_nonce + Uint128::one());
+            selected_transactions.push(transaction);
+        }
+        self.transactions
+            .retain(|tx| !invalid_hashes.contains(&tx.hash));
+        // later txs of sender can't be executed until nonce of dropped tx is sent again
+        for address in invalid_senders {
+            let account_nonce = digichain
+                .get_account(address)
+                .map_or(Uint128::zero(), |account| account.tx_nonce);
+            self.demote_transactions(address, account_nonce);
+        }
         selected_transactions
     }
 
@@ -232,3 +437,153 @@ impl Mempool {
         }
     }
 }
+
+fn get_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        transaction::TxType,
+        types::{HexString, TxExecutionResult},
+    };
+    use ethers::{
+        core::k256::ecdsa::SigningKey,
+        signers::{LocalWallet, Signer, Wallet},
+        types::U256,
+    };
+
+    fn get_tx(wallet: &Wallet<SigningKey>, nonce: u128) -> Transaction {
+        let mut tx = Transaction {
+            hash: String::new(),
+            created_at: 1700000000u64,
+            chain_id: "digichain".to_string(),
+            timestamp: 0u64,
+            nonce: Uint128::from(nonce),
+            from: wallet.address(),
+            data: HexString::from_vec(vec![1u8]),
+            signature: Signature {
+                r: U256::zero(),
+                s: U256::zero(),
+                v: 0u64,
+            },
+            block_number: 0u64,
+            result: TxExecutionResult::None,
+            tx_type: TxType::CrossChainRequest(HexString::from_vec(vec![2u8])),
+            proof: None,
+        };
+        tx.sign(wallet).unwrap();
+        tx
+    }
+
+    fn get_nonces(txs: &Vec<Transaction>) -> Vec<u128> {
+        let mut nonces: Vec<u128> = txs.iter().map(|tx| tx.nonce.u128()).collect();
+        nonces.sort();
+        nonces
+    }
+
+    #[test]
+    fn queues_tx_until_nonce_gap_is_filled() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut mempool = Mempool::new();
+        assert!(!mempool
+            .add_transaction(&get_tx(&wallet, 2u128), Uint128::zero())
+            .unwrap());
+        assert!(mempool
+            .add_transaction(&get_tx(&wallet, 0u128), Uint128::zero())
+            .unwrap());
+        assert_eq!(get_nonces(&mempool.transactions), vec![0u128]);
+        assert_eq!(mempool.queued_at.len(), 1usize);
+
+        // duplicate of ready and of queued nonce
+        assert!(mempool
+            .add_transaction(&get_tx(&wallet, 0u128), Uint128::zero())
+            .is_err());
+        assert!(mempool
+            .add_transaction(&get_tx(&wallet, 2u128), Uint128::zero())
+            .is_err());
+
+        assert!(mempool
+            .add_transaction(&get_tx(&wallet, 1u128), Uint128::zero())
+            .unwrap());
+        assert_eq!(get_nonces(&mempool.transactions), vec![0u128, 1u128, 2u128]);
+        assert!(mempool.queued_transactions.is_empty());
+        assert!(mempool.queued_at.is_empty());
+
+        // executed already
+        assert!(mempool
+            .add_transaction(&get_tx(&wallet, 0u128), Uint128::from(3u128))
+            .is_err());
+    }
+
+    #[test]
+    fn requeues_txs_behind_dropped_invalid_tx() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut digichain = DigiChain::default();
+        digichain.chain_id = "digichain".to_string();
+        let mut mempool = Mempool::new();
+        let mut invalid_tx = get_tx(&wallet, 0u128);
+        invalid_tx.data = HexString::from_vec(vec![3u8]);
+        for tx in [invalid_tx, get_tx(&wallet, 1u128), get_tx(&wallet, 2u128)] {
+            mempool.add_transaction(&tx, Uint128::zero()).unwrap();
+        }
+
+        assert!(mempool.select_txs_randomly(digichain.clone()).is_empty());
+        assert!(mempool.transactions.is_empty());
+        assert_eq!(
+            mempool
+                .queued_transactions
+                .get(&wallet.address())
+                .unwrap()
+                .keys()
+                .map(|nonce| nonce.u128())
+                .collect::<Vec<u128>>(),
+            vec![1u128, 2u128]
+        );
+        assert_eq!(mempool.queued_at.len(), 2usize);
+
+        // nonce sent again fills gap
+        assert!(mempool
+            .add_transaction(&get_tx(&wallet, 0u128), Uint128::zero())
+            .unwrap());
+        assert_eq!(get_nonces(&mempool.transactions), vec![0u128, 1u128, 2u128]);
+        assert!(mempool.queued_at.is_empty());
+        let selected = mempool.select_txs_randomly(digichain);
+        for (idx, tx) in selected.iter().enumerate() {
+            assert_eq!(tx.nonce.u128(), idx as u128);
+        }
+    }
+
+    #[test]
+    fn drops_expired_queued_txs() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut mempool = Mempool::new();
+        let expired_tx = get_tx(&wallet, 3u128);
+        let queued_tx = get_tx(&wallet, 5u128);
+        for tx in [&expired_tx, &queued_tx] {
+            assert!(!mempool.add_transaction(tx, Uint128::zero()).unwrap());
+        }
+        mempool
+            .queued_at
+            .insert(expired_tx.hash.clone(), get_now() - MaxQueuedTxAge);
+
+        mempool.drop_expired_queued_transactions();
+        let queue = mempool.queued_transactions.get(&wallet.address()).unwrap();
+        assert_eq!(queue.len(), 1usize);
+        assert!(queue.contains_key(&queued_tx.nonce));
+        assert!(!mempool.queued_at.contains_key(&expired_tx.hash));
+        assert!(mempool.queued_at.contains_key(&queued_tx.hash));
+
+        mempool
+            .queued_at
+            .insert(queued_tx.hash.clone(), get_now() - MaxQueuedTxAge);
+        mempool.drop_expired_queued_transactions();
+        assert!(mempool.queued_transactions.is_empty());
+        assert!(mempool.queued_at.is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    digichain::DigiChain,
+    types::{Address, TxCrossChainMessageParams},
+};
+use std::error::Error as StdError;
+
+// native handler of inbound crosschain messages, registered against handler address
+pub type MessageHandler =
+    fn(&mut DigiChain, &TxCrossChainMessageParams) -> Result<Vec<u8>, Box<dyn StdError>>;
+
+// dispatches inbound message to handler registered for params.handler
+pub fn dispatch_message(
+    digichain: &mut DigiChain,
+    params: &TxCrossChainMessageParams,
+) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let handler = get_handler(digichain, params.handler);
+    if handler.is_none() {
+        return Err(format!("no handler registered for {:?}", params.handler).into());
+    }
+    // no lock is held while handler runs, it gets whole chain
+    handler.unwrap()(digichain, params)
+}
+
+// handlers governance can register against an address, by kind
+pub fn get_native_handler(kind: &str) -> Option<MessageHandler> {
+    match kind {
+        "Accept" => Some(accept_message),
+        _ => None,
+    }
+}
+
+// acks delivery without acting on it, message is kept as execution result for apps to read
+fn accept_message(
+    _digichain: &mut DigiChain,
+    params: &TxCrossChainMessageParams,
+) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let res = params.message.to_vec();
+    if res.is_err() {
+        return Err(format!("{:?}", res.err()).into());
+    }
+    Ok(res.unwrap())
+}
+
+fn get_handler(digichain: &DigiChain, handler: Address) -> Option<MessageHandler> {
+    let binding = digichain.crosschain.read().unwrap();
+    binding.get_message_handler(handler)
+}
@@ -1,23 +1,42 @@
 use crate::{
     acccount::Account,
-    constants::{LockedFundRequest, UnLockedFailedRequest, UnLockedWithdrawRequest},
-    crosschain::CrossChainExecutionResult,
+    chain_adapter::{ChainAdapter, ChainType},
+    checkpoint::CheckpointMsg,
+    constants::{
+        BatchWithdrawRequest, LockedFundRequest, MessageRequest, UnLockedFailedRequest,
+        UnLockedWithdrawRequest,
+    },
+    crosschain::{
+        BridgeReserve, CrossChain, CrossChainExecutionResult, CrossChainRequestStatus,
+        CrossChainTokenResult, CrossChainTokenStatus, DelayedMint, ReserveFlow,
+    },
     digichain::DigiChain,
+    eip712::{get_proposal_typed_data, get_typed_data_digest},
+    light_client::{decode_header, verify_locked_fund_proof, ReceiptProof},
+    message::{dispatch_message, get_native_handler},
     token::DigiToken,
     types::{
-        AddContractConfigParams, AddTokenParams, Address, CrossChainRequestParams, HexString,
-        KYCParams, TxCrossChainReplyParams, TxCrossChainRequestParams, TxExecutionResult,
-        UpdateTokensPriceParams,
+        AddContractConfigParams, AddTokenParams, Address, BatchConfig, CrossChainRequestParams,
+        CrossChainWithdrawPayload, DisableChainParams, FeeConfig, HexString, KYCParams,
+        RateLimitConfig, RegisterMessageHandlerParams, RemoveContractConfigParams, TokenId,
+        TxCrossChainMessageParams, TxCrossChainReplyParams, TxCrossChainRequestParams,
+        TxExecutionResult, UpdateBatchConfigParams, UpdateFeeConfigParams, UpdateLightClientParams,
+        UpdateRateLimitParams, UpdateTokensPriceParams,
     },
     utils::{
-        address_to_str, decode_crosschain_request_type_data, encode_crosschain_request_type_data,
+        decode_crosschain_request_type_data, encode_crosschain_request_type_data,
+        get_crosschain_withdraw_msg_hash, get_crosschain_withdraw_payload, uint_to_u128,
+        uint_to_u64, uint_to_u8,
     },
+    validators::Validator,
 };
 use cosmwasm_std::Uint128;
 
 use ethers::{
     abi::Tokenizable,
-    types::{Signature, U256},
+    core::k256::ecdsa::SigningKey,
+    signers::{Signer, Wallet},
+    types::{Signature, H256, U256},
     utils::hex::encode,
 };
 use router_wasm_bindings::ethabi::{
@@ -47,6 +66,15 @@ pub enum ProposalType {
     AddChainToken,
     UpdateChainToken,
     AddContractConfig,
+    UpdateContractConfig,
+    DisableChain,
+    RemoveContractConfig,
+    UpdateRateLimit,
+    RegisterMessageHandler,
+    UpdateFeeConfig,
+    UpdateBatchConfig,
+    UpdateLightClient,
+    AnchorCheckpoint,
     UpdateTokensPrice,
     None,
 }
@@ -88,13 +116,29 @@ impl RawProposal {
             data: self.data.clone(),
             nonce: self.nonce,
             block_number: Default::default(),
+            // proposer signature is over typed data, votes are added by validators while attesting
             signature: self.signature,
-            validtors_signature: vec![(self.proposed_by, self.signature)],
+            validtors_signature: vec![],
             timestamp: Default::default(),
             result: TxExecutionResult::None,
             extra_data: self.extra_data.clone(),
         }
     }
+
+    // eip-712 typed data covering every field of proposal, proposal hash alone leaves proposer,
+    // nonce and extra data unsigned
+    pub fn get_signing_digest(&self) -> Result<H256, Box<dyn StdError>> {
+        get_proposal_typed_data(self).and_then(|typed_data| get_typed_data_digest(&typed_data))
+    }
+
+    pub fn get_signer(&self) -> Result<Address, Box<dyn StdError>> {
+        let digest = self.get_signing_digest()?;
+        let res = self.signature.recover(digest);
+        if res.is_err() {
+            return Err(format!("invalid signature: {:?}", res.err()).into());
+        }
+        Ok(res.unwrap())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -104,11 +148,206 @@ pub struct CrossChainWithdrawMsg {
     pub src_nonce: Uint128,
     pub payload: HexString,
     pub sigs: Vec<Signature>,
+    #[serde(default)]
+    pub signers: Vec<Address>, // signers[i] signed sigs[i]
+    #[serde(default)]
+    pub validator_set_id: String,
+    #[serde(default)]
+    pub bls_sigs: Vec<(Address, HexString)>, // individual bls sig, aggregated once proposal got majority
+    #[serde(default)]
+    pub aggregated_signature: Option<HexString>,
+    #[serde(default)]
+    pub aggregated_signers: Vec<Address>,
+    #[serde(default)]
+    pub chain_type: ChainType, // of dst chain, decides digest signed by validators
+    #[serde(default)]
+    pub entries: Vec<(Uint128, HexString)>, // (src_nonce,payload) of batched withdraws, payload carries their merkle root
+}
+
+impl CrossChainWithdrawMsg {
+    pub fn new(
+        dst_chain_id: String,
+        src_chain_id: String,
+        src_nonce: Uint128,
+        payload: HexString,
+        validator_set_id: String,
+        chain_type: ChainType,
+    ) -> CrossChainWithdrawMsg {
+        CrossChainWithdrawMsg {
+            dst_chain_id,
+            src_chain_id,
+            src_nonce,
+            payload,
+            sigs: vec![],
+            signers: vec![],
+            validator_set_id,
+            bls_sigs: vec![],
+            aggregated_signature: None,
+            aggregated_signers: vec![],
+            chain_type,
+            entries: vec![],
+        }
+    }
+
+    // adapter of dst chain, cosmwasm digest carries addresses with prefix of chain
+    pub fn get_digest(&self, adapter: &dyn ChainAdapter) -> Result<H256, Box<dyn StdError>> {
+        if adapter.chain_type() != self.chain_type {
+            return Err(format!(
+                "adapter of {:?} can't sign withdraw of {:?}",
+                adapter.chain_type(),
+                self.chain_type
+            )
+            .into());
+        }
+        adapter.get_withdraw_digest(
+            self.src_chain_id.clone(),
+            self.dst_chain_id.clone(),
+            self.src_nonce,
+            &self.payload,
+        )
+    }
+
+    pub fn get_msg_hash(&self) -> Result<[u8; 32], Box<dyn StdError>> {
+        get_crosschain_withdraw_msg_hash(
+            self.src_chain_id.clone(),
+            self.dst_chain_id.clone(),
+            self.src_nonce,
+            &self.payload,
+        )
+    }
+
+    // sign with every scheme enabled on this node
+    pub fn attest(
+        &mut self,
+        validator: &Validator,
+        adapter: &dyn ChainAdapter,
+    ) -> Result<bool, Box<dyn StdError>> {
+        #[cfg(feature = "ecdsa")]
+        self.sign(&validator.wallet, adapter)?;
+        #[cfg(feature = "bls")]
+        self.sign_bls(validator)?;
+        Ok(true)
+    }
+
+    pub fn sign(
+        &mut self,
+        wallet: &Wallet<SigningKey>,
+        adapter: &dyn ChainAdapter,
+    ) -> Result<Signature, Box<dyn StdError>> {
+        if self.signers.contains(&wallet.address()) {
+            return Err(format!("already signed").into());
+        }
+        let digest = self.get_digest(adapter)?;
+        let res = wallet.sign_hash(digest);
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        let signature = res.unwrap();
+        self.sigs.push(signature);
+        self.signers.push(wallet.address());
+        Ok(signature)
+    }
+
+    // same check as gateway on dst chain, returns signers whose signature is valid
+    pub fn verify_signatures(
+        &self,
+        adapter: &dyn ChainAdapter,
+    ) -> Result<Vec<Address>, Box<dyn StdError>> {
+        if self.sigs.len() != self.signers.len() {
+            return Err(format!("sigs and signers length mismatch").into());
+        }
+        let digest = self.get_digest(adapter)?;
+        let mut valid_signers: Vec<Address> = Vec::new();
+        for idx in 0..self.sigs.len() {
+            let res = self.sigs[idx].recover(digest);
+            if res.is_ok() && res.unwrap() == self.signers[idx] {
+                valid_signers.push(self.signers[idx]);
+            }
+        }
+        Ok(valid_signers)
+    }
+
+    #[cfg(feature = "bls")]
+    pub fn sign_bls(&mut self, validator: &Validator) -> Result<HexString, Box<dyn StdError>> {
+        let address = validator.acccount.address;
+        if self.bls_sigs.iter().any(|(signer, _)| *signer == address) {
+            return Err(format!("already signed").into());
+        }
+        if validator.bls_secret_key.is_none() {
+            return Err(format!("bls key not registered").into());
+        }
+        let msg_hash = self.get_msg_hash()?;
+        let sig = crate::bls::sign(validator.bls_secret_key.as_ref().unwrap(), &msg_hash);
+        self.bls_sigs.push((address, sig.clone()));
+        Ok(sig)
+    }
+
+    #[cfg(feature = "bls")]
+    pub fn aggregate_bls_signatures(&mut self) -> Result<HexString, Box<dyn StdError>> {
+        if self.bls_sigs.len() == 0usize {
+            return Err(format!("no bls signature to aggregate").into());
+        }
+        let sigs = self
+            .bls_sigs
+            .iter()
+            .map(|(_, sig)| sig.clone())
+            .collect::<Vec<HexString>>();
+        let aggregated_signature = crate::bls::aggregate_signatures(&sigs)?;
+        self.aggregated_signature = Some(aggregated_signature.clone());
+        self.aggregated_signers = self.bls_sigs.iter().map(|(signer, _)| *signer).collect();
+        Ok(aggregated_signature)
+    }
+
+    // public_keys[i] should be bls public key of aggregated_signers[i]
+    #[cfg(feature = "bls")]
+    pub fn verify_aggregated_signature(
+        &self,
+        public_keys: &Vec<HexString>,
+    ) -> Result<bool, Box<dyn StdError>> {
+        if self.aggregated_signature.is_none() {
+            return Err(format!("signatures not aggregated").into());
+        }
+        if public_keys.len() != self.aggregated_signers.len() {
+            return Err(format!("public_keys and aggregated_signers length mismatch").into());
+        }
+        let msg_hash = self.get_msg_hash()?;
+        crate::bls::verify_aggregated_signature(
+            &msg_hash,
+            self.aggregated_signature.as_ref().unwrap(),
+            public_keys,
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ExtraData {
     WithdrawData(CrossChainWithdrawMsg),
+    ReceiptProof(ReceiptProof),
+    Checkpoint(CheckpointMsg),
+}
+
+impl ExtraData {
+    // signatures of validators are added while attesting, proposer signs extra data without them
+    pub fn get_signing_data(&self) -> ExtraData {
+        match self {
+            ExtraData::WithdrawData(withdraw) => {
+                let mut withdraw = withdraw.clone();
+                withdraw.sigs = vec![];
+                withdraw.signers = vec![];
+                withdraw.bls_sigs = vec![];
+                withdraw.aggregated_signature = None;
+                withdraw.aggregated_signers = vec![];
+                ExtraData::WithdrawData(withdraw)
+            }
+            ExtraData::Checkpoint(checkpoint) => {
+                let mut checkpoint = checkpoint.clone();
+                checkpoint.sigs = vec![];
+                checkpoint.signers = vec![];
+                ExtraData::Checkpoint(checkpoint)
+            }
+            ExtraData::ReceiptProof(_) => self.clone(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -168,6 +407,15 @@ impl Proposal {
         format!("0x{}", sha256::digest(serialized_tx))
     }
 
+    // buffered proposal is already recorded under its hash in block it was buffered in,
+    // its deferred execution is recorded under a hash distinct to executing block
+    pub fn get_deferred_execution_hash(&self, block_number: u64) -> String {
+        format!(
+            "0x{}",
+            sha256::digest(format!("{}:{}", self.hash, block_number))
+        )
+    }
+
     pub fn is_valid(&self, digichain: DigiChain) -> bool {
         let accounts = digichain.accounts.read().unwrap();
         // if tx not signed for this chain
@@ -180,24 +428,25 @@ impl Proposal {
             //< 70% majority
             return false;
         }
+        drop(binding);
 
-        // self.validtors_signature.len() >
-        // TODO: uncomment
-        // is this tx is signed by from user or not
-        // let msg_bytes = self.get_raw_proposal().to_byte();
-        // if msg_bytes.is_err() {
-        //     return false;
-        // }
-        // let msg_bytes = msg_bytes.unwrap();
-        // let res = self.signature.recover(msg_bytes);
-        // if res.is_err() {
-        //     return false;
-        // }
-        // let original_signer = res.unwrap();
-        // if original_signer != self.proposed_by {
-        //     return false;
-        // }
-        true
+        // signed by proposer, or by validator of node which created it while executing tx of proposer
+        let res = self.get_raw_proposal().get_signer();
+        if res.is_err() {
+            return false;
+        }
+        let signer = res.unwrap();
+        signer == self.proposed_by || digichain.is_validator(signer)
+    }
+
+    pub fn sign(&mut self, wallet: &Wallet<SigningKey>) -> Result<Signature, Box<dyn StdError>> {
+        let digest = self.get_raw_proposal().get_signing_digest()?;
+        let res = wallet.sign_hash(digest);
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        self.signature = res.unwrap();
+        Ok(self.signature)
     }
 
     pub fn is_signed(&self, address: Address) -> bool {
@@ -243,8 +492,19 @@ impl Proposal {
             ProposalType::UserKYC => self.user_kyc(timestamp, digichain),
             ProposalType::AddToken => self.add_token(timestamp, digichain),
             ProposalType::AddContractConfig => self.add_contract_config(timestamp, digichain),
+            ProposalType::UpdateContractConfig => self.update_contract_config(timestamp, digichain),
+            ProposalType::DisableChain => self.disable_chain(timestamp, digichain),
+            ProposalType::UpdateRateLimit => self.update_rate_limit(timestamp, digichain),
+            ProposalType::RegisterMessageHandler => {
+                self.register_message_handler(timestamp, digichain)
+            }
+            ProposalType::UpdateFeeConfig => self.update_fee_config(timestamp, digichain),
+            ProposalType::UpdateBatchConfig => self.update_batch_config(timestamp, digichain),
+            ProposalType::UpdateLightClient => self.update_light_client(timestamp, digichain),
+            ProposalType::AnchorCheckpoint => self.add_checkpoint(digichain),
+            ProposalType::RemoveContractConfig => self.remove_contract_config(timestamp, digichain),
             ProposalType::CrossChainRequest(data) => {
-                self.add_crosschain_request(timestamp, data.clone(), digichain)
+                self.add_crosschain_request(block_number, timestamp, data.clone(), digichain)
             }
             ProposalType::UpdateTokensPrice => self.update_tokens_price(timestamp, digichain),
             _ => Err("proposal type not exist".into()),
@@ -314,6 +574,13 @@ impl Proposal {
             return Err(format!("decoding data: {:?}", params.err()).into());
         }
         let params: AddTokenParams = params.unwrap();
+        {
+            // denoms are case sensitive, asset keeps original case
+            let mut crosschain = digichain.crosschain.write().unwrap();
+            for (chain_id, asset) in params.chain_token_mapping.iter() {
+                crosschain.register_chain_asset(chain_id.clone(), asset.clone());
+            }
+        }
         let mut tokens = digichain.token_list.write().unwrap();
         let token = DigiToken::new(
             params.name.clone(),
@@ -321,6 +588,7 @@ impl Proposal {
             params.decimal,
             params.price,
             params.chain_token_mapping.clone(),
+            params.chain_decimals.clone(),
         );
         tokens.insert(token.id.clone(), token.clone());
 
@@ -362,8 +630,404 @@ impl Proposal {
         Ok(vec![])
     }
 
+    fn update_contract_config(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        // same payload as add contract config
+        let params = get_add_contract_config(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: AddContractConfigParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        let res = crosschain.modify_contract_config(
+            params.chain_id.clone(),
+            params.contract_address.clone(),
+            params.start_block.clone(),
+            params.chain_type,
+        );
+        if res.is_err() {
+            return Err(format!("update_contract_config: {:?}", res.err()).into());
+        }
+        println!(
+            "Contract Config Updated || ChainId: {}, ChainType: {}, ContractAdddress: {}, StartBlock: {}",
+            params.chain_id, params.chain_type, params.contract_address, params.start_block
+        );
+        Ok(vec![])
+    }
+
+    fn disable_chain(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_disable_chain_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: DisableChainParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        let res = crosschain.set_chain_disabled(params.chain_id.clone(), params.disabled);
+        if res.is_err() {
+            return Err(format!("disable_chain: {:?}", res.err()).into());
+        }
+        println!(
+            "Chain Disabled || ChainId: {}, Disabled: {}",
+            params.chain_id, params.disabled
+        );
+        Ok(vec![])
+    }
+
+    fn remove_contract_config(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_remove_contract_config_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: RemoveContractConfigParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        let res = crosschain.remove_contract_config(params.chain_id.clone());
+        if res.is_err() {
+            return Err(format!("remove_contract_config: {:?}", res.err()).into());
+        }
+        println!("Contract Config Removed || ChainId: {}", params.chain_id);
+        Ok(vec![])
+    }
+
+    fn register_message_handler(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_register_message_handler_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: RegisterMessageHandlerParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        if params.kind.is_empty() {
+            crosschain.remove_message_handler(params.handler);
+            println!("Message Handler Removed || Handler: {:?}", params.handler);
+            return Ok(vec![]);
+        }
+        let res = get_native_handler(&params.kind);
+        if res.is_none() {
+            return Err(format!("unknown message handler kind {}", params.kind).into());
+        }
+        crosschain.register_message_handler(params.handler, res.unwrap());
+        println!(
+            "Message Handler Registered || Handler: {:?}, Kind: {}",
+            params.handler, params.kind
+        );
+        Ok(vec![])
+    }
+
+    fn update_rate_limit(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_update_rate_limit_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: UpdateRateLimitParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        crosschain.set_rate_limit(
+            params.chain_id.clone(),
+            params.token_id.clone(),
+            params.config.clone(),
+        );
+        println!(
+            "Rate Limit Updated || ChainId: {}, TokenId: {}, Window: {}, Cap: {}, AccountCap: {}",
+            params.chain_id,
+            params.token_id,
+            params.config.window,
+            params.config.cap,
+            params.config.account_cap
+        );
+        Ok(vec![])
+    }
+
+    fn update_fee_config(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_update_fee_config_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: UpdateFeeConfigParams = params.unwrap();
+        if let Some(fee_token) = &params.config.fee_token {
+            if !digichain.token_list.read().unwrap().contains_key(fee_token) {
+                return Err(format!("fee token not found {:?}", fee_token).into());
+            }
+        }
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        crosschain.set_fee_config(params.chain_id.clone(), params.config.clone());
+        println!(
+            "Fee Config Updated || ChainId: {}, Flat: {}, Bps: {}, FeeToken: {:?}",
+            params.chain_id, params.config.flat, params.config.bps, params.config.fee_token
+        );
+        Ok(vec![])
+    }
+
+    fn update_batch_config(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_update_batch_config_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: UpdateBatchConfigParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        crosschain.set_batch_config(params.chain_id.clone(), params.config.clone());
+        println!(
+            "Batch Config Updated || ChainId: {}, MaxBlocks: {}, MaxSize: {}",
+            params.chain_id, params.config.max_blocks, params.config.max_size
+        );
+        Ok(vec![])
+    }
+
+    // checkpoint signed by quorum, ready to be anchored on external chain
+    fn add_checkpoint(&mut self, digichain: &mut DigiChain) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let checkpoint = match &self.extra_data {
+            Some(ExtraData::Checkpoint(checkpoint)) => checkpoint.clone(),
+            Some(_) => return Err("unknown extra data".into()),
+            None => return Err("missing extra data".into()),
+        };
+        let res = digichain
+            .get_block(checkpoint.block_number)
+            .and_then(|block| digichain.get_checkpoint(&block));
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        let block_checkpoint = res.unwrap();
+        if block_checkpoint.block_hash != checkpoint.block_hash
+            || block_checkpoint.state_root != checkpoint.state_root
+        {
+            return Err(
+                format!("checkpoint doesn't match block {}", checkpoint.block_number).into(),
+            );
+        }
+        // only signatures of current validators count toward quorum
+        let valid_signers = checkpoint
+            .verify_signatures()?
+            .into_iter()
+            .filter(|signer| digichain.is_validator(*signer))
+            .collect::<Vec<Address>>();
+        let validators = digichain.validators.read().unwrap().len();
+        if valid_signers.len() * 100 < 70 * validators {
+            return Err(format!(
+                "checkpoint signed by {} of {} validators",
+                valid_signers.len(),
+                validators
+            )
+            .into());
+        }
+        // only valid signatures are anchored
+        let mut signed = CheckpointMsg::new(
+            checkpoint.chain_id.clone(),
+            checkpoint.block_number,
+            checkpoint.block_hash,
+            checkpoint.state_root,
+        );
+        for idx in 0..checkpoint.signers.len() {
+            if valid_signers.contains(&checkpoint.signers[idx])
+                && !signed.signers.contains(&checkpoint.signers[idx])
+            {
+                signed.sigs.push(checkpoint.sigs[idx]);
+                signed.signers.push(checkpoint.signers[idx]);
+            }
+        }
+        let block_number = signed.block_number;
+        digichain
+            .crosschain
+            .write()
+            .unwrap()
+            .add_checkpoint(signed)?;
+        println!(
+            "Checkpoint Added || BlockNumber: {}, BlockHash: {:?}",
+            block_number, checkpoint.block_hash
+        );
+        Ok(vec![])
+    }
+
+    // src chain headers voted by validators, receipt proofs of inbound requests are checked against them
+    fn update_light_client(
+        &mut self,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_update_light_client_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: UpdateLightClientParams = params.unwrap();
+        let chain_id = params.chain_id;
+        let mut decoded_headers = Vec::new();
+        for header in params.headers {
+            let res = decode_header(&header);
+            if res.is_err() {
+                return Err(format!("decoding header: {:?}", res.err()).into());
+            }
+            decoded_headers.push(res.unwrap());
+        }
+        decoded_headers.sort_by_key(|header| header.number);
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        for header in decoded_headers {
+            let number = header.number;
+            let res = crosschain.add_header(chain_id.clone(), header);
+            if res.is_err() {
+                return Err(format!("header {}: {:?}", number, res.err()).into());
+            }
+        }
+        let latest = crosschain.get_latest_header(chain_id.clone());
+        println!(
+            "Light Client Updated || ChainId: {}, LatestHeader: {:?}",
+            chain_id,
+            latest.map(|header| header.number)
+        );
+        Ok(vec![])
+    }
+
+    // funds unlocked from gateway of dst chain, request is final
+    fn ack_withdraw(
+        &self,
+        digichain: &DigiChain,
+        crosschain: &mut CrossChain,
+        src_chain_id: String,
+        src_nonce: Uint128,
+        executed_by: Option<Address>,
+        dst_tx_hash: String,
+        block_number: u64,
+    ) {
+        let res = crosschain.get_request(src_chain_id.clone(), src_nonce);
+        if res.is_err() {
+            return;
+        }
+        let mut res = res.unwrap();
+        // acked already, or never left this chain
+        if res.get_status().map_or(false, |status| status.is_final()) {
+            return;
+        }
+        if let (Some(dst_chain_id), Some(payload)) = (res.dst_chain_id.clone(), &res.payload) {
+            // payload is in format of dst chain
+            let withdraw = crosschain
+                .get_chain_adapter(dst_chain_id.clone())
+                .and_then(|adapter| Ok((adapter.decode_withdraw_payload(payload)?, adapter)));
+            if withdraw.is_ok() {
+                let (withdraw, adapter) = withdraw.unwrap();
+                let token_idx_mp = digichain.chain_id_to_token_mp.read().unwrap();
+                let token_list = digichain.token_list.read().unwrap();
+                for idx in 0..withdraw.tokens.len() {
+                    let token_id = token_idx_mp.get(&(
+                        dst_chain_id.clone(),
+                        adapter.format_address(withdraw.tokens[idx]).to_lowercase(),
+                    ));
+                    if token_id.is_none() {
+                        continue;
+                    }
+                    let token_id = token_id.unwrap();
+                    // payload carries amounts in dst chain decimals
+                    let amount =
+                        token_list
+                            .get(token_id)
+                            .map_or(Ok(withdraw.amounts[idx]), |token| {
+                                token.from_chain_amount(dst_chain_id.clone(), withdraw.amounts[idx])
+                            });
+                    if amount.is_err() {
+                        continue;
+                    }
+                    let res = crosschain.record_reserve_flow(
+                        dst_chain_id.clone(),
+                        token_id.clone(),
+                        ReserveFlow::Withdrawn,
+                        amount.unwrap(),
+                    );
+                    if res.is_err() {
+                        println!(
+                            "Reserve Update Failed || SrcChainId: {}, SrcNonce: {}, Error: {:?}",
+                            src_chain_id,
+                            src_nonce,
+                            res.err()
+                        );
+                    }
+                }
+            }
+        }
+        // escrowed fees go to validator that executed it on dst chain
+        if executed_by.is_some() && res.fees.len() > 0 {
+            let validator = executed_by.unwrap();
+            let mut token_list = digichain.token_list.write().unwrap();
+            for fee in res.fees.iter_mut() {
+                if fee.paid_to.is_some() || fee.refunded {
+                    continue;
+                }
+                if let Some(token) = token_list.get_mut(&fee.token_id) {
+                    token.mint(validator, fee.amount);
+                    fee.paid_to = Some(validator);
+                }
+            }
+        }
+        let mut binding = crosschain.requests.write().unwrap();
+        res.ack_tx_hash = Some(self.hash.clone());
+        res.dst_tx_hash = Some(dst_tx_hash);
+        res.update_status(CrossChainRequestStatus::ExecutedOnDst, block_number);
+        res.update_status(CrossChainRequestStatus::Acked, block_number);
+        binding.insert((src_chain_id, src_nonce.to_string()), res);
+    }
+
+    // gateway nonces are sequential across all inbound requests of a chain,
+    // replayed nonces are rejected and future ones wait till missing nonces arrive
+    fn accept_inbound_nonce(
+        &self,
+        crosschain: &mut CrossChain,
+        src_chain_id: String,
+        src_contract: Address,
+        src_nonce: Uint128,
+        src_block_number: u64,
+    ) -> Result<bool, Box<dyn StdError>> {
+        if !crosschain.is_contract_registered(src_chain_id.clone(), src_contract) {
+            return Err(format!("contract not registered").into());
+        }
+        if crosschain.is_chain_disabled(src_chain_id.clone()) {
+            return Err(format!("chain {} is disabled", src_chain_id).into());
+        }
+        let res = crosschain.check_inbound_nonce(src_chain_id.clone(), src_nonce);
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        if !res.unwrap() {
+            crosschain.buffer_inbound_request(src_chain_id.clone(), src_nonce, self.clone());
+            println!(
+                "CrossChain Request Buffered || SrcChainId: {}, SrcNonce: {}, Missing: {:?}",
+                src_chain_id,
+                src_nonce,
+                crosschain.get_inbound_nonce_gaps(src_chain_id.clone())
+            );
+            return Ok(false);
+        }
+
+        // update_contract_config
+        let res = crosschain.update_contract_config(src_chain_id, src_nonce, src_block_number);
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        Ok(true)
+    }
+
     fn add_crosschain_request(
         &mut self,
+        block_number: u64,
         timestamp: u64,
         data: HexString,
         digichain: &mut DigiChain,
@@ -375,18 +1039,23 @@ impl Proposal {
         let info: crate::types::CrossChainRequestTypeData = info.unwrap();
 
         // emitted from this chain, and will be executed on dst chain soon by crossweaver
-        if info.src_chain_id == digichain.chain_id && info.dst_nonce == Uint128::from(0u128) {
-            // update
+        // replies never carry withdraw data
+        if info.src_chain_id == digichain.chain_id
+            && info.dst_nonce == Uint128::from(0u128)
+            && self.extra_data.is_some()
+        {
+            // update, refund request is already recorded along with failed request it belongs to
             let mut crosschain = digichain.crosschain.write().unwrap();
-            crosschain.add_request(
-                info.src_chain_id,
-                info.src_nonce,
-                CrossChainExecutionResult {
-                    src_tx_hash: self.hash.clone(),
-                    dst_tx_hash: None,
-                    ack_tx_hash: None,
-                },
-            );
+            let mut request = crosschain
+                .get_request(info.src_chain_id.clone(), info.src_nonce)
+                .unwrap_or(CrossChainExecutionResult::new(self.hash.clone()));
+            request.src_tx_hash = self.hash.clone();
+            request.dst_chain_id = Some(info.dst_chain_id.clone());
+            if let Some(ExtraData::WithdrawData(data)) = &self.extra_data {
+                request.payload = Some(data.payload.clone());
+            }
+            request.update_status(CrossChainRequestStatus::Attested, block_number);
+            crosschain.add_request(info.src_chain_id, info.src_nonce, request);
             let res = match &self.proposal_type {
                 ProposalType::CrossChainRequest(data) => {
                     let mut mempool = digichain.mempool.write().unwrap();
@@ -397,13 +1066,7 @@ impl Proposal {
                     let res: crate::types::CrossChainRequestTypeData = decode_data.unwrap();
                     let extra_data_res: Result<CrossChainWithdrawMsg, Box<dyn StdError>> =
                         match &self.extra_data {
-                            Some(ExtraData::WithdrawData(data)) => Ok(CrossChainWithdrawMsg {
-                                dst_chain_id: data.dst_chain_id.clone(),
-                                src_chain_id: data.src_chain_id.clone(),
-                                src_nonce: data.src_nonce,
-                                payload: data.payload.clone(),
-                                sigs: data.sigs.clone(),
-                            }),
+                            Some(ExtraData::WithdrawData(data)) => Ok(data.clone()),
                             Some(_) => Err("unknown extra data".into()),
                             None => Err("missing extra data".into()),
                         };
@@ -411,7 +1074,21 @@ impl Proposal {
                     if extra_data_res.is_err() {
                         return Err(format!("{:?}", extra_data_res.err()).into());
                     }
-                    mempool.add_crosschain_request(res.validator, &extra_data_res.unwrap());
+                    // bls sigs got aggregated while attesting
+                    let withdraw_msg = extra_data_res.unwrap();
+                    crosschain.assign_request(
+                        withdraw_msg.src_chain_id.clone(),
+                        withdraw_msg.src_nonce,
+                        res.validator,
+                        block_number,
+                    );
+                    crosschain.update_request_status(
+                        withdraw_msg.src_chain_id.clone(),
+                        withdraw_msg.src_nonce,
+                        CrossChainRequestStatus::AssignedToValidator,
+                        block_number,
+                    );
+                    mempool.add_crosschain_request(res.validator, &withdraw_msg);
                     Ok(vec![])
                 }
                 _ => Err(format!("invalid proposal type").into()),
@@ -422,160 +1099,440 @@ impl Proposal {
         let mut crosschain = digichain.crosschain.write().unwrap();
         let res = crosschain.get_request(info.src_chain_id.clone(), info.src_nonce);
         // emitted from this chain, and reply came here
-        if info.request_type == UnLockedWithdrawRequest {
+        if info.request_type == UnLockedWithdrawRequest
+            || info.request_type == BatchWithdrawRequest
+            || (info.request_type == MessageRequest && info.src_chain_id == digichain.chain_id)
+        {
             if res.is_err() {
                 return Err(format!("Invalid Request: {:?}", res.err()).into());
             }
-            let mut res = res.unwrap();
-            let params = get_crr_unlocked_event_payload_params(self.data.clone());
+            let res = res.unwrap();
+            // replayed reply, request and its batch are closed already
+            if res.get_status().map_or(false, |status| status.is_final()) {
+                return Ok(vec![]);
+            }
+            // reply is emitted by gateway of dst chain, in its format
+            let params = crosschain
+                .get_chain_adapter(info.dst_chain_id.clone())
+                .and_then(|adapter| adapter.decode_reply_payload(&self.data));
             if params.is_err() {
                 return Err(format!("decoding data: {:?}", params.err()).into());
             }
             let params: TxCrossChainReplyParams = params.unwrap();
-            let mut binding = crosschain.requests.write().unwrap();
-            res.ack_tx_hash = Some(self.hash.clone());
-            res.dst_tx_hash = Some(params.dst_tx_hash.clone());
-            binding.insert((info.src_chain_id.clone(), info.src_nonce.to_string()), res);
+            // escrowed fees go to validator that executed it on dst chain
+            let executed_by = crosschain.get_broadcaster(info.src_chain_id.clone(), info.src_nonce);
+            // batch is executed on dst chain along with every withdraw in it
+            for src_nonce in res.batch_entries.iter() {
+                self.ack_withdraw(
+                    digichain,
+                    &mut crosschain,
+                    info.src_chain_id.clone(),
+                    *src_nonce,
+                    executed_by,
+                    params.dst_tx_hash.clone(),
+                    block_number,
+                );
+            }
+            self.ack_withdraw(
+                digichain,
+                &mut crosschain,
+                info.src_chain_id.clone(),
+                info.src_nonce,
+                executed_by,
+                params.dst_tx_hash.clone(),
+                block_number,
+            );
+            crosschain.remove_assignment(info.src_chain_id.clone(), info.src_nonce);
+            digichain
+                .mempool
+                .write()
+                .unwrap()
+                .remove_crosschain_request(info.src_chain_id, info.src_nonce);
             return Ok(vec![]);
         }
 
-        // emitted from other chain, means user locked there fund and LockedFund event emitted from there
-        if info.request_type == LockedFundRequest {
-            let params = get_crr_locked_event_payload_params(self.data.clone());
+        // emitted from other chain, message to handler registered here
+        if info.request_type == MessageRequest {
+            let params = crosschain
+                .get_chain_adapter(info.src_chain_id.clone())
+                .and_then(|adapter| adapter.decode_message_payload(&self.data));
             if params.is_err() {
                 return Err(format!("decoding data: {:?}", params.err()).into());
             }
-            let params: TxCrossChainRequestParams = params.unwrap();
-            //TODO: if any how this tx fails return fund to src chain
-            // recipient is registered?
-            // sender is registered
-            // dst_chainid == self.chainid
-            // this nonce process already or
-            // verify proposal got enough votes and votes are valid
-
-            // src_chain_id is registered or not
-            // src_contract registered?
-
-            // update_contract_config
-            let res = crosschain.update_contract_config(
+            let params: TxCrossChainMessageParams = params.unwrap();
+            let res = self.accept_inbound_nonce(
+                &mut crosschain,
                 params.src_chain_id.clone(),
+                params.src_contract,
                 params.src_nonce,
                 params.src_block_number,
             );
             if res.is_err() {
                 return Err(format!("{:?}", res.err()).into());
             }
-            let token_idx_mp = digichain.chain_id_to_token_mp.read().unwrap();
-            if !crosschain.is_contract_registered(params.src_chain_id.clone(), params.src_contract)
-            {
-                return Err(format!("contract not registered").into());
+            if !res.unwrap() {
+                return Ok(vec![]);
+            }
+            let mut request = CrossChainExecutionResult::new(params.src_tx_hash.clone());
+            request.dst_tx_hash = Some(self.hash.clone());
+            request.dst_chain_id = Some(digichain.chain_id.clone());
+            request.payload = Some(get_crosschain_withdraw_payload(
+                MessageRequest,
+                vec![],
+                vec![],
+                params.sender,
+                params.handler,
+                params.message.to_vec().unwrap_or(vec![]),
+            ));
+            drop(crosschain);
+            // nonce is consumed even if handler fails, failure is kept in request
+            let res = dispatch_message(digichain, &params);
+            if res.is_err() {
+                request.error = Some(format!("{:?}", res.err()));
+                request.update_status(CrossChainRequestStatus::Failed, block_number);
+            } else {
+                request.update_status(CrossChainRequestStatus::ExecutedOnDst, block_number);
+            }
+            println!(
+                "CrossChain Message Delivered || SrcChainId: {}, SrcNonce: {}, Handler: {:?}, Error: {:?}",
+                info.src_chain_id, info.src_nonce, params.handler, request.error
+            );
+            digichain.crosschain.write().unwrap().add_request(
+                info.src_chain_id.clone(),
+                info.src_nonce,
+                request,
+            );
+            return Ok(vec![]);
+        }
+
+        // emitted from other chain, means user locked there fund and LockedFund event emitted from there
+        if info.request_type == LockedFundRequest {
+            let params = crosschain
+                .get_chain_adapter(info.src_chain_id.clone())
+                .and_then(|adapter| adapter.decode_locked_fund_payload(&self.data));
+            if params.is_err() {
+                return Err(format!("decoding data: {:?}", params.err()).into());
+            }
+            let params: TxCrossChainRequestParams = params.unwrap();
+            // receipt proof, if attached, has to prove LockedFund event against light client headers
+            let mut proven = false;
+            if let Some(ExtraData::ReceiptProof(proof)) = &self.extra_data {
+                let res = verify_locked_fund_proof(&crosschain, &params, proof);
+                if res.is_err() {
+                    return Err(format!("invalid receipt proof: {:?}", res.err()).into());
+                }
+                proven = true;
+            }
+            //TODO: if any how this tx fails return fund to src chain
+            // recipient is registered?
+            // sender is registered
+            // dst_chainid == self.chainid
+            // this nonce process already or
+            // verify proposal got enough votes and votes are valid
+
+            // src_chain_id is registered or not
+            // src_contract registered?
+
+            let res = self.accept_inbound_nonce(
+                &mut crosschain,
+                params.src_chain_id.clone(),
+                params.src_contract,
+                params.src_nonce,
+                params.src_block_number,
+            );
+            if res.is_err() {
+                return Err(format!("{:?}", res.err()).into());
+            }
+            if !res.unwrap() {
+                // buffered, executed once missing nonces arrive
+                return Ok(vec![]);
+            }
+            let res = crosschain.get_chain_adapter(params.src_chain_id.clone());
+            if res.is_err() {
+                return Err(format!("{:?}", res.err()).into());
             }
+            let adapter = res.unwrap();
+            let token_idx_mp = digichain.chain_id_to_token_mp.read().unwrap();
             let mut result: String = String::new();
             let mut dst_tokens: Vec<Address> = Vec::new();
             let mut dst_amounts: Vec<Uint128> = Vec::new();
+            let mut token_results: Vec<CrossChainTokenResult> = Vec::new();
             let tokens = params.tokens;
             let amounts = params.amounts;
-            for idx in 0..tokens.len() {
-                // tokens[idx] is register if yes then tokenid?
-                let res = token_idx_mp.get(&(
-                    params.src_chain_id.clone(),
-                    address_to_str(tokens[idx]).to_lowercase(),
-                ));
-                if res.is_none() {
-                    dst_tokens.push(tokens[idx]);
-                    dst_amounts.push(amounts[idx]);
-                    result.push_str(&format!(
-                        "[{idx}] {:x} token not registered || ",
-                        tokens[idx]
+            // executed request can't be rolled back, so everything which can fail is checked before first mint
+            // (idx, token_id, amount, delayed) of tokens to mint
+            let mut mints: Vec<(usize, TokenId, Uint128, bool)> = Vec::new();
+            let mut reserves: HashMap<TokenId, BridgeReserve> = HashMap::new();
+            let mut minted: HashMap<TokenId, Uint128> = HashMap::new();
+            {
+                let token_list = digichain.token_list.read().unwrap();
+                for idx in 0..tokens.len() {
+                    // tokens[idx] is register if yes then tokenid?
+                    let res = token_idx_mp.get(&(
+                        params.src_chain_id.clone(),
+                        adapter.format_address(tokens[idx]).to_lowercase(),
                     ));
-                    continue;
-                    // this token not registerd, need to refund on src chain
+                    if res.is_none() {
+                        dst_tokens.push(tokens[idx]);
+                        dst_amounts.push(amounts[idx]);
+                        token_results.push(CrossChainTokenResult {
+                            token: tokens[idx],
+                            amount: amounts[idx],
+                            status: CrossChainTokenStatus::Unregistered,
+                        });
+                        result.push_str(&format!(
+                            "[{idx}] {:x} token not registered || ",
+                            tokens[idx]
+                        ));
+                        continue;
+                        // this token not registerd, need to refund on src chain
+                    }
+                    let token_id = res.unwrap();
+                    let res = token_list.get(token_id);
+                    if res.is_none() {
+                        dst_tokens.push(tokens[idx]);
+                        dst_amounts.push(amounts[idx]);
+                        token_results.push(CrossChainTokenResult {
+                            token: tokens[idx],
+                            amount: amounts[idx],
+                            status: CrossChainTokenStatus::Unregistered,
+                        });
+                        result.push_str(&format!("[{idx}] {:x} token not mapped || ", tokens[idx]));
+                        continue;
+                    }
+                    let token = res.unwrap();
+                    let res = token.from_chain_amount(params.src_chain_id.clone(), amounts[idx]);
+                    if res.is_err() {
+                        // amount can't be represented in digichain decimals, refund it as is
+                        dst_tokens.push(tokens[idx]);
+                        dst_amounts.push(amounts[idx]);
+                        token_results.push(CrossChainTokenResult {
+                            token: tokens[idx],
+                            amount: amounts[idx],
+                            status: CrossChainTokenStatus::Dust,
+                        });
+                        result.push_str(&format!(
+                            "[{idx}] {:x} token amount is dust || ",
+                            tokens[idx]
+                        ));
+                        continue;
+                    }
+                    let amount = res.unwrap();
+                    let reserve = reserves.entry(token_id.clone()).or_insert_with(|| {
+                        crosschain.get_reserve(params.src_chain_id.clone(), token_id.clone())
+                    });
+                    // earlier tokens of same request count against rate limit too
+                    let pending = minted.get(token_id).cloned().unwrap_or_default();
+                    let res = pending.checked_add(amount);
+                    if res.is_err() {
+                        return Err(format!("mint amount overflow: {:?}", res.err()).into());
+                    }
+                    let res = crosschain.check_rate_limit(
+                        params.src_chain_id.clone(),
+                        token_id.clone(),
+                        params.recipient,
+                        res.unwrap(),
+                        block_number,
+                    );
+                    // funds are locked on src chain whether minted now or later
+                    let delayed = res.is_err();
+                    let res = reserve.record(ReserveFlow::Locked, amount);
+                    if res.is_err() {
+                        return Err(format!("{:?}", res.err()).into());
+                    }
+                    if delayed {
+                        // over cap, minted after window passes instead of rejecting locked funds
+                        mints.push((idx, token_id.clone(), amount, true));
+                        token_results.push(CrossChainTokenResult {
+                            token: tokens[idx],
+                            amount: amounts[idx],
+                            status: CrossChainTokenStatus::Delayed,
+                        });
+                        result
+                            .push_str(&format!("[{idx}] {:x} token mint delayed || ", tokens[idx]));
+                        continue;
+                    }
+                    let res = reserve.record(ReserveFlow::Minted, amount);
+                    if res.is_err() {
+                        return Err(format!("{:?}", res.err()).into());
+                    }
+                    // recipient balance has to fit all mints of request
+                    let res = token
+                        .get_balance_of(params.recipient)
+                        .checked_add(pending + amount);
+                    if res.is_err() {
+                        return Err(format!("balance overflow: {:?}", res.err()).into());
+                    }
+                    minted.insert(token_id.clone(), pending + amount);
+                    mints.push((idx, token_id.clone(), amount, false));
+                    token_results.push(CrossChainTokenResult {
+                        token: tokens[idx],
+                        amount: amounts[idx],
+                        status: CrossChainTokenStatus::Minted,
+                    });
+                    result.push_str(&format!("[{idx}] {:x} token minted || ", tokens[idx]));
+                }
+            }
+            // unlock not minted tokens back to depositor on src chain
+            let mut refund_payload = None;
+            if dst_amounts.len() > 0 {
+                let res = adapter.encode_withdraw_payload(&CrossChainWithdrawPayload {
+                    request_type: UnLockedFailedRequest,
+                    tokens: dst_tokens,
+                    amounts: dst_amounts,
+                    depositor: params.recipient,
+                    recipient: params.depositor,
+                    message: HexString::from_vec(vec![]),
+                });
+                if res.is_err() {
+                    return Err(format!("refund payload: {:?}", res.err()).into());
                 }
-                let token_id = res.unwrap();
-                let mut token_list = digichain.token_list.write().unwrap();
-                let res = token_list.get_mut(token_id);
-                if res.is_none() {
-                    dst_tokens.push(tokens[idx]);
-                    dst_amounts.push(amounts[idx]);
-                    result.push_str(&format!("{:x} token not mapped || ", tokens[idx]));
+                refund_payload = Some(res.unwrap());
+            }
+
+            // nothing below fails
+            for (token_id, reserve) in reserves {
+                crosschain.set_reserve(params.src_chain_id.clone(), token_id, reserve);
+            }
+            let mut token_list = digichain.token_list.write().unwrap();
+            for (idx, token_id, amount, delayed) in mints {
+                if delayed {
+                    let window = crosschain
+                        .get_rate_limit(params.src_chain_id.clone(), token_id.clone())
+                        .map_or(0u64, |config| config.window);
+                    crosschain.queue_delayed_mint(DelayedMint {
+                        src_chain_id: info.src_chain_id.clone(),
+                        src_nonce: info.src_nonce,
+                        idx,
+                        token_id,
+                        recipient: params.recipient,
+                        amount,
+                        release_at: block_number.saturating_add(window),
+                    });
                     continue;
                 }
-                let token = res.unwrap();
-                token.mint(params.recipient.clone(), amounts[idx]);
-                return Ok(vec![]);
+                token_list
+                    .get_mut(&token_id)
+                    .unwrap()
+                    .mint(params.recipient.clone(), amount);
+                crosschain.record_rate_limit_usage(
+                    params.src_chain_id.clone(),
+                    token_id,
+                    params.recipient,
+                    amount,
+                    block_number,
+                );
             }
-            if dst_amounts.len() > 0 {
+            drop(token_list);
+            let mut request = CrossChainExecutionResult::new(params.src_tx_hash.clone());
+            request.dst_tx_hash = Some(self.hash.clone());
+            request.tokens = token_results;
+            request.proven = proven;
+            request.dst_chain_id = Some(digichain.chain_id.clone());
+            request.payload = Some(get_crosschain_withdraw_payload(
+                LockedFundRequest,
+                tokens,
+                amounts,
+                params.depositor,
+                params.recipient,
+                vec![],
+            ));
+            request.update_status(CrossChainRequestStatus::ExecutedOnDst, block_number);
+            if let Some(payload) = refund_payload {
                 //TODO: chain will processes block request after certain days, after accumulating block funds
-                // create outbound proposal data
-                // let dst_tokens_token = dst_tokens
-                //     .into_iter()
-                //     .map(|token| Token::Address(EthRouterAddress::from_slice(&token.as_bytes())))
-                //     .collect();
-                // let dst_amounts_token = dst_amounts
-                //     .into_iter()
-                //     .map(|amount| {
-                //         Token::Uint(router_wasm_bindings::ethabi::ethereum_types::U256::from(
-                //             amount,
-                //         ))
-                //     })
-                //     .collect();
-                // let dst_tokens_token_arr = Token::Array(dst_tokens_token);
-                // let dst_amounts_token_arr = Token::Array(dst_amounts_token);
-                // let from_token =
-                //     Token::Address(EthRouterAddress::from_slice(&params.depositor.as_bytes()));
-                // let recipient_token =
-                //     Token::Address(EthRouterAddress::from_slice(&params.recipient.as_bytes()));
-
-                // let msg_token = Token::Bytes(vec![]);
-                // let msg = rencode(&[
-                //     dst_tokens_token_arr,
-                //     dst_amounts_token_arr,
-                //     from_token,
-                //     recipient_token,
-                //     msg_token,
-                // ]);
-                // let self_nonce = crosschain.self_nonce;
-                // // drop(crosschain);
-                // let udata = digichain.get_cmp_ccr_data(
-                //     &mut crosschain,
-                //     UnLockedFailedRequest,
-                //     digichain.chain_id.clone(),
-                //     params.src_chain_id,
-                //     params.src_nonce,
-                //     self_nonce,
-                // );
-                // let proposal = Proposal::new(
-                //     digichain.chain_id.clone(),
-                //     ProposalType::CrossChainRequest(udata.clone()),
-                //     self.proposed_by,
-                //     timestamp,
-                //     HexString::from_vec(msg),
-                //     self.nonce,
-                //     self.block_number,
-                // );
-                // let mut mempool = digichain.mempool.write().unwrap();
-                // let _ = mempool.add_proposal(ProposalType::CrossChainRequest(udata), proposal);
+                let refund_nonce = crosschain.increase_nonce();
+                let udata = digichain.get_cmp_ccr_data(
+                    UnLockedFailedRequest,
+                    digichain.chain_id.clone(),
+                    params.src_chain_id.clone(),
+                    refund_nonce,
+                    Uint128::from(0u128),
+                );
+                let mut proposal = Proposal::new(
+                    digichain.chain_id.clone(),
+                    ProposalType::CrossChainRequest(udata.clone()),
+                    self.proposed_by,
+                    timestamp,
+                    HexString::from_vec(vec![]),
+                    self.nonce,
+                    block_number,
+                    Some(ExtraData::WithdrawData(CrossChainWithdrawMsg::new(
+                        params.src_chain_id.clone(),
+                        digichain.chain_id.clone(),
+                        refund_nonce,
+                        payload.clone(),
+                        digichain.get_validator_set_id(),
+                        adapter.chain_type(),
+                    ))),
+                );
+                let mut refund_request = CrossChainExecutionResult::new(proposal.hash.clone());
+                refund_request.refund_of = Some((info.src_chain_id.clone(), info.src_nonce));
+                refund_request.dst_chain_id = Some(params.src_chain_id.clone());
+                refund_request.payload = Some(payload);
+                refund_request.update_status(CrossChainRequestStatus::Initiated, block_number);
+                crosschain.add_request(digichain.chain_id.clone(), refund_nonce, refund_request);
+                request.refund_nonce = Some(refund_nonce);
+                request.update_status(CrossChainRequestStatus::Failed, block_number);
+
+                digichain.sign_proposal(&mut proposal);
+                let mut mempool = digichain.mempool.write().unwrap();
+                let _ = mempool.add_proposal(ProposalType::CrossChainRequest(udata), proposal);
+                println!(
+                    "CrossChain Refund Requested || SrcChainId: {}, SrcNonce: {}, RefundNonce: {}, {}",
+                    info.src_chain_id, info.src_nonce, refund_nonce, result
+                );
             }
 
-            let mut binding = crosschain.requests.write().unwrap();
-            binding.insert(
-                (info.src_chain_id.clone(), info.src_nonce.to_string()),
-                CrossChainExecutionResult {
-                    src_tx_hash: params.src_tx_hash,
-                    dst_tx_hash: Some(self.hash.clone()),
-                    ack_tx_hash: None,
-                },
-            );
+            crosschain.add_request(info.src_chain_id.clone(), info.src_nonce, request);
             return Ok(vec![]);
         }
 
         // reply of failed tx request back to chain
         if info.request_type == UnLockedFailedRequest {
+            if res.is_err() {
+                return Err(format!("Invalid Request: {:?}", res.err()).into());
+            }
             let mut res = res.unwrap();
-            let mut binding = crosschain.requests.write().unwrap();
+            // replayed reply, refund and failed request are closed already
+            if res.get_status().map_or(false, |status| status.is_final()) {
+                return Ok(vec![]);
+            }
+            // reply is emitted by gateway of dst chain, in its format
+            let params = crosschain
+                .get_chain_adapter(info.dst_chain_id.clone())
+                .and_then(|adapter| adapter.decode_reply_payload(&self.data));
+            if params.is_err() {
+                return Err(format!("decoding data: {:?}", params.err()).into());
+            }
+            let params: TxCrossChainReplyParams = params.unwrap();
             res.ack_tx_hash = Some(self.hash.clone());
-            res.dst_tx_hash = res.dst_tx_hash;
-            binding.insert((info.src_chain_id.clone(), info.src_nonce.to_string()), res);
+            res.dst_tx_hash = Some(params.dst_tx_hash.clone());
+            res.update_status(CrossChainRequestStatus::ExecutedOnDst, block_number);
+            res.update_status(CrossChainRequestStatus::Acked, block_number);
+            // funds are unlocked on src chain, close failed request as well
+            if let Some((failed_chain_id, failed_nonce)) = res.refund_of.clone() {
+                let failed_request = crosschain.get_request(failed_chain_id.clone(), failed_nonce);
+                if failed_request.is_ok() {
+                    let mut failed_request = failed_request.unwrap();
+                    failed_request.ack_tx_hash = Some(self.hash.clone());
+                    for token_result in failed_request.tokens.iter_mut() {
+                        if token_result.status == CrossChainTokenStatus::Unregistered
+                            || token_result.status == CrossChainTokenStatus::Dust
+                        {
+                            token_result.status = CrossChainTokenStatus::Refunded;
+                        }
+                    }
+                    failed_request.update_status(CrossChainRequestStatus::Refunded, block_number);
+                    crosschain.add_request(failed_chain_id, failed_nonce, failed_request);
+                }
+            }
+            crosschain.add_request(info.src_chain_id.clone(), info.src_nonce, res);
+            crosschain.remove_assignment(info.src_chain_id.clone(), info.src_nonce);
+            digichain
+                .mempool
+                .write()
+                .unwrap()
+                .remove_crosschain_request(info.src_chain_id, info.src_nonce);
             return Ok(vec![]);
         }
         // will never reach here
@@ -613,19 +1570,320 @@ impl RawProposal {
     }
 }
 
-fn get_kyc_data(hex_data: HexString) -> Result<KYCParams, Box<dyn StdError>> {
+pub(crate) fn get_kyc_data(hex_data: HexString) -> Result<KYCParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::String, //name
+            ParamType::String, //aadhar
+            ParamType::String, //upi_id
+            ParamType::String, //mobile_no
+            ParamType::String, //address
+            ParamType::String, //country
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    //name
+    let res = decoded_data[0].clone().into_string();
+    if res.is_none() {
+        return Err("name is none".into());
+    }
+    let name = res.unwrap();
+
+    //aadhar_no
+    let res = decoded_data[1].clone().into_string();
+    if res.is_none() {
+        return Err("aadhar_no is none".into());
+    }
+    let aadhar_no = res.unwrap();
+
+    //upi_id
+    let res = decoded_data[2].clone().into_string();
+    if res.is_none() {
+        return Err("upi_id is none".into());
+    }
+    let upi_id = res.unwrap();
+
+    //mobile
+    let res = decoded_data[3].clone().into_string();
+    if res.is_none() {
+        return Err("mobile is none".into());
+    }
+    let mobile = res.unwrap();
+
+    //address
+    let res = decoded_data[4].clone().into_string();
+    if res.is_none() {
+        return Err("address is none".into());
+    }
+    let address = res.unwrap();
+
+    //country
+    let res = decoded_data[5].clone().into_string();
+    if res.is_none() {
+        return Err("country is none".into());
+    }
+    let country = res.unwrap();
+
+    Ok(KYCParams {
+        name,
+        upi_id,
+        address,
+        aadhar_no,
+        mobile,
+        country,
+    })
+}
+
+fn get_add_token_data(hex_data: HexString) -> Result<AddTokenParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let data = data.unwrap();
+    let mut param_types = vec![
+        ParamType::String,                              //name
+        ParamType::String,                              //symbol
+        ParamType::Uint(8),                             //symbol
+        ParamType::Uint(256),                           //price is f64 * 10^9
+        ParamType::Array(Box::new(ParamType::String)),  // vec<string> - array of chain ids
+        ParamType::Array(Box::new(ParamType::String)), // vec<string> - array of corrosponding chain token
+        ParamType::Array(Box::new(ParamType::Uint(8))), // vec<u8> - array of corrosponding chain token decimals
+    ];
+    let mut decoded_data = decode(&param_types, &data);
+    if decoded_data.is_err() {
+        // older proposals don't carry chain decimals
+        param_types.pop();
+        decoded_data = decode(&param_types, &data);
+    }
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    //name
+    let res = decoded_data[0].clone().into_string();
+    if res.is_none() {
+        return Err("name is none".into());
+    }
+    let name = res.unwrap();
+
+    //symbol
+    let res = decoded_data[1].clone().into_string();
+    if res.is_none() {
+        return Err("symbol is none".into());
+    }
+    let symbol = res.unwrap();
+
+    //decimal
+    let res = decoded_data[2].clone().into_uint();
+    if res.is_none() {
+        return Err("decimal is none".into());
+    }
+    let decimal = uint_to_u8(res.unwrap(), "decimal")?;
+
+    //price
+    let res = decoded_data[3].clone().into_uint();
+    if res.is_none() {
+        return Err("price is none".into());
+    }
+    let price = uint_to_u128(res.unwrap(), "price")?;
+
+    // vec<chain_ids>
+    let res = decoded_data[4].clone().into_array();
+    if res.is_none() {
+        return Err("vec<chain_ids> is none".into());
+    }
+    let chain_ids_mp = res
+        .unwrap()
+        .into_iter()
+        .filter_map(|token| token.clone().into_string())
+        .collect::<Vec<String>>();
+
+    // vec<token_address>
+    let res = decoded_data[5].clone().into_array();
+    if res.is_none() {
+        return Err("vec<token_address> is none".into());
+    }
+    let token_address_mp = res
+        .unwrap()
+        .into_iter()
+        .filter_map(|token| token.clone().into_string())
+        .collect::<Vec<String>>();
+
+    if token_address_mp.len() != chain_ids_mp.len() {
+        return Err("token_address_mp.len() != chain_ids_mp.len() ".into());
+    }
+
+    // vec<decimal>, same as decimal on every chain if missing
+    let mut chain_decimals_mp = vec![decimal; chain_ids_mp.len()];
+    if decoded_data.len() > 6 {
+        let res = decoded_data[6].clone().into_array();
+        if res.is_none() {
+            return Err("vec<decimal> is none".into());
+        }
+        chain_decimals_mp = Vec::new();
+        for token in res.unwrap() {
+            let res = token.into_uint();
+            if res.is_none() {
+                continue;
+            }
+            chain_decimals_mp.push(uint_to_u8(res.unwrap(), "chain decimal")?);
+        }
+        if chain_decimals_mp.len() != chain_ids_mp.len() {
+            return Err("chain_decimals_mp.len() != chain_ids_mp.len() ".into());
+        }
+    }
+
+    let mut chain_token_mapping = HashMap::new();
+    let mut chain_decimals = HashMap::new();
+    for idx in 0..token_address_mp.len() {
+        chain_token_mapping.insert(
+            chain_ids_mp.get(idx).unwrap().clone(),
+            token_address_mp.get(idx).unwrap().clone(),
+        );
+        chain_decimals.insert(
+            chain_ids_mp.get(idx).unwrap().clone(),
+            *chain_decimals_mp.get(idx).unwrap(),
+        );
+    }
+    Ok(AddTokenParams {
+        name,
+        symbol,
+        decimal,
+        price,
+        chain_token_mapping,
+        chain_decimals,
+    })
+}
+
+fn get_add_contract_config(
+    hex_data: HexString,
+) -> Result<AddContractConfigParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::String,
+            ParamType::Uint(8),
+            ParamType::String,
+            ParamType::Uint(64),
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // chain id
+    let res = decoded_data[0].clone().into_string();
+    if res.is_none() {
+        return Err("contract_address is none".into());
+    }
+    let chain_id = res.unwrap();
+
+    // chain_type
+    let res = decoded_data[1].clone().into_uint();
+    if res.is_none() {
+        return Err("start_block is none".into());
+    }
+    let chain_type = uint_to_u8(res.unwrap(), "chain_type")?;
+
+    // contract_address
+    let res = decoded_data[2].clone().into_string();
+    if res.is_none() {
+        return Err("contract_address is none".into());
+    }
+    let contract_address = res.unwrap();
+
+    // start_block
+    let res = decoded_data[3].clone().into_uint();
+    if res.is_none() {
+        return Err("start_block is none".into());
+    }
+    let start_block = uint_to_u64(res.unwrap(), "start_block")?;
+
+    Ok(AddContractConfigParams {
+        contract_address,
+        start_block,
+        chain_id,
+        chain_type,
+    })
+}
+
+fn get_disable_chain_params(hex_data: HexString) -> Result<DisableChainParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(&[ParamType::String, ParamType::Bool], &data.unwrap());
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // chain id
+    let res = decoded_data[0].clone().into_string();
+    if res.is_none() {
+        return Err("chain_id is none".into());
+    }
+    let chain_id = res.unwrap();
+
+    // disabled
+    let res = decoded_data[1].clone().into_bool();
+    if res.is_none() {
+        return Err("disabled is none".into());
+    }
+    let disabled = res.unwrap();
+
+    Ok(DisableChainParams { chain_id, disabled })
+}
+
+fn get_remove_contract_config_params(
+    hex_data: HexString,
+) -> Result<RemoveContractConfigParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(&[ParamType::String], &data.unwrap());
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // chain id
+    let res = decoded_data[0].clone().into_string();
+    if res.is_none() {
+        return Err("chain_id is none".into());
+    }
+    let chain_id = res.unwrap();
+
+    Ok(RemoveContractConfigParams { chain_id })
+}
+
+fn get_register_message_handler_params(
+    hex_data: HexString,
+) -> Result<RegisterMessageHandlerParams, Box<dyn StdError>> {
     let data = hex_data.to_vec();
     if data.is_err() {
         return Err(format!("{:?}", data.err()).into());
     }
     let decoded_data = decode(
         &[
-            ParamType::String, //name
-            ParamType::String, //aadhar
-            ParamType::String, //upi_id
-            ParamType::String, //mobile_no
-            ParamType::String, //address
-            ParamType::String, //country
+            ParamType::Address, // handler
+            ParamType::String,  // kind
         ],
         &data.unwrap(),
     );
@@ -634,71 +1892,107 @@ fn get_kyc_data(hex_data: HexString) -> Result<KYCParams, Box<dyn StdError>> {
     }
     let decoded_data = decoded_data.unwrap();
 
-    //name
-    let res = decoded_data[0].clone().into_string();
+    // handler
+    let res = decoded_data[0].clone().into_address();
     if res.is_none() {
-        return Err("name is none".into());
+        return Err("handler is none".into());
     }
-    let name = res.unwrap();
+    let handler = res.unwrap();
 
-    //aadhar_no
+    // kind
     let res = decoded_data[1].clone().into_string();
     if res.is_none() {
-        return Err("aadhar_no is none".into());
+        return Err("kind is none".into());
     }
-    let aadhar_no = res.unwrap();
+    let kind = res.unwrap();
 
-    //upi_id
-    let res = decoded_data[2].clone().into_string();
+    Ok(RegisterMessageHandlerParams {
+        handler: Address::from_slice(&handler.as_bytes()),
+        kind,
+    })
+}
+
+fn get_update_rate_limit_params(
+    hex_data: HexString,
+) -> Result<UpdateRateLimitParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::String,    // chain_id
+            ParamType::String,    // token_id
+            ParamType::Uint(64),  // window
+            ParamType::Uint(256), // cap
+            ParamType::Uint(256), // account_cap
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // chain id
+    let res = decoded_data[0].clone().into_string();
     if res.is_none() {
-        return Err("upi_id is none".into());
+        return Err("chain_id is none".into());
     }
-    let upi_id = res.unwrap();
+    let chain_id = res.unwrap();
 
-    //mobile
-    let res = decoded_data[3].clone().into_string();
+    // token id
+    let res = decoded_data[1].clone().into_string();
     if res.is_none() {
-        return Err("mobile is none".into());
+        return Err("token_id is none".into());
     }
-    let mobile = res.unwrap();
+    let token_id = res.unwrap();
 
-    //address
-    let res = decoded_data[4].clone().into_string();
+    // window
+    let res = decoded_data[2].clone().into_uint();
     if res.is_none() {
-        return Err("address is none".into());
+        return Err("window is none".into());
     }
-    let address = res.unwrap();
+    let window = uint_to_u64(res.unwrap(), "window")?;
 
-    //country
-    let res = decoded_data[5].clone().into_string();
+    // cap
+    let res = decoded_data[3].clone().into_uint();
     if res.is_none() {
-        return Err("country is none".into());
+        return Err("cap is none".into());
     }
-    let country = res.unwrap();
+    let cap = uint_to_u128(res.unwrap(), "cap")?;
 
-    Ok(KYCParams {
-        name,
-        upi_id,
-        address,
-        aadhar_no,
-        mobile,
-        country,
+    // account_cap
+    let res = decoded_data[4].clone().into_uint();
+    if res.is_none() {
+        return Err("account_cap is none".into());
+    }
+    let account_cap = uint_to_u128(res.unwrap(), "account_cap")?;
+
+    Ok(UpdateRateLimitParams {
+        chain_id,
+        token_id,
+        config: RateLimitConfig {
+            window,
+            cap,
+            account_cap,
+        },
     })
 }
 
-fn get_add_token_data(hex_data: HexString) -> Result<AddTokenParams, Box<dyn StdError>> {
+fn get_update_fee_config_params(
+    hex_data: HexString,
+) -> Result<UpdateFeeConfigParams, Box<dyn StdError>> {
     let data = hex_data.to_vec();
     if data.is_err() {
         return Err(format!("{:?}", data.err()).into());
     }
     let decoded_data = decode(
         &[
-            ParamType::String,                             //name
-            ParamType::String,                             //symbol
-            ParamType::Uint(8),                            //symbol
-            ParamType::Uint(256),                          //price is f64 * 10^9
-            ParamType::Array(Box::new(ParamType::String)), // vec<string> - array of chain ids
-            ParamType::Array(Box::new(ParamType::String)), // vec<string> - array of corrosponding chain token
+            ParamType::String,    // chain_id
+            ParamType::Uint(256), // flat
+            ParamType::Uint(64),  // bps
+            ParamType::String,    // fee_token, empty for bridged token
         ],
         &data.unwrap(),
     );
@@ -707,89 +2001,63 @@ fn get_add_token_data(hex_data: HexString) -> Result<AddTokenParams, Box<dyn Std
     }
     let decoded_data = decoded_data.unwrap();
 
-    //name
+    // chain id
     let res = decoded_data[0].clone().into_string();
     if res.is_none() {
-        return Err("name is none".into());
+        return Err("chain_id is none".into());
     }
-    let name = res.unwrap();
+    let chain_id = res.unwrap();
 
-    //symbol
-    let res = decoded_data[1].clone().into_string();
+    // flat
+    let res = decoded_data[1].clone().into_uint();
     if res.is_none() {
-        return Err("symbol is none".into());
+        return Err("flat is none".into());
     }
-    let symbol = res.unwrap();
+    let flat = uint_to_u128(res.unwrap(), "flat")?;
 
-    //decimal
+    // bps
     let res = decoded_data[2].clone().into_uint();
     if res.is_none() {
-        return Err("decimal is none".into());
-    }
-    let decimal = res.unwrap().as_u32() as u8;
-
-    //price
-    let res = decoded_data[3].clone().into_uint();
-    if res.is_none() {
-        return Err("price is none".into());
+        return Err("bps is none".into());
     }
-    let price = Uint128::from(res.unwrap().as_u128());
-
-    // vec<chain_ids>
-    let res = decoded_data[4].clone().into_array();
-    if res.is_none() {
-        return Err("vec<chain_ids> is none".into());
+    let bps = uint_to_u64(res.unwrap(), "bps")?;
+    if bps > 10000u64 {
+        return Err(format!("bps {} is more than 10000", bps).into());
     }
-    let chain_ids_mp = res
-        .unwrap()
-        .into_iter()
-        .filter_map(|token| token.clone().into_string())
-        .collect::<Vec<String>>();
 
-    // vec<token_address>
-    let res = decoded_data[5].clone().into_array();
+    // fee token
+    let res = decoded_data[3].clone().into_string();
     if res.is_none() {
-        return Err("vec<token_address> is none".into());
-    }
-    let token_address_mp = res
-        .unwrap()
-        .into_iter()
-        .filter_map(|token| token.clone().into_string())
-        .collect::<Vec<String>>();
-
-    if token_address_mp.len() != chain_ids_mp.len() {
-        return Err("token_address_mp.len() != chain_ids_mp.len() ".into());
+        return Err("fee_token is none".into());
     }
+    let fee_token = res.unwrap();
 
-    let mut chain_token_mapping = HashMap::new();
-    for idx in 0..token_address_mp.len() {
-        chain_token_mapping.insert(
-            chain_ids_mp.get(idx).unwrap().clone(),
-            token_address_mp.get(idx).unwrap().clone(),
-        );
-    }
-    Ok(AddTokenParams {
-        name,
-        symbol,
-        decimal,
-        price,
-        chain_token_mapping,
+    Ok(UpdateFeeConfigParams {
+        chain_id,
+        config: FeeConfig {
+            flat,
+            bps,
+            fee_token: if fee_token.is_empty() {
+                None
+            } else {
+                Some(fee_token)
+            },
+        },
     })
 }
 
-fn get_add_contract_config(
+fn get_update_batch_config_params(
     hex_data: HexString,
-) -> Result<AddContractConfigParams, Box<dyn StdError>> {
+) -> Result<UpdateBatchConfigParams, Box<dyn StdError>> {
     let data = hex_data.to_vec();
     if data.is_err() {
         return Err(format!("{:?}", data.err()).into());
     }
     let decoded_data = decode(
         &[
-            ParamType::String,
-            ParamType::Uint(8),
-            ParamType::String,
-            ParamType::Uint(64),
+            ParamType::String,   // chain_id
+            ParamType::Uint(64), // max_blocks
+            ParamType::Uint(64), // max_size, 0 removes batching
         ],
         &data.unwrap(),
     );
@@ -801,40 +2069,74 @@ fn get_add_contract_config(
     // chain id
     let res = decoded_data[0].clone().into_string();
     if res.is_none() {
-        return Err("contract_address is none".into());
+        return Err("chain_id is none".into());
     }
     let chain_id = res.unwrap();
 
-    // chain_type
+    // max blocks
     let res = decoded_data[1].clone().into_uint();
     if res.is_none() {
-        return Err("start_block is none".into());
+        return Err("max_blocks is none".into());
     }
-    let chain_type = res.unwrap().as_u32() as u8;
+    let max_blocks = uint_to_u64(res.unwrap(), "max_blocks")?;
 
-    // contract_address
-    let res = decoded_data[2].clone().into_string();
+    // max size
+    let res = decoded_data[2].clone().into_uint();
     if res.is_none() {
-        return Err("contract_address is none".into());
+        return Err("max_size is none".into());
     }
-    let contract_address = res.unwrap();
+    let max_size = uint_to_u64(res.unwrap(), "max_size")?;
 
-    // start_block
-    let res = decoded_data[3].clone().into_uint();
+    Ok(UpdateBatchConfigParams {
+        chain_id,
+        config: BatchConfig {
+            max_blocks,
+            max_size,
+        },
+    })
+}
+
+fn get_update_light_client_params(
+    hex_data: HexString,
+) -> Result<UpdateLightClientParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::String,                            // chain_id
+            ParamType::Array(Box::new(ParamType::Bytes)), // vec<rlp encoded header>
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // chain id
+    let res = decoded_data[0].clone().into_string();
     if res.is_none() {
-        return Err("start_block is none".into());
+        return Err("chain_id is none".into());
     }
-    let start_block = res.unwrap();
+    let chain_id = res.unwrap();
 
-    Ok(AddContractConfigParams {
-        contract_address,
-        start_block: start_block.as_u64(),
-        chain_id,
-        chain_type,
-    })
+    // headers
+    let res = decoded_data[1].clone().into_array();
+    if res.is_none() {
+        return Err("headers is none".into());
+    }
+    let headers = res
+        .unwrap()
+        .into_iter()
+        .filter_map(|header| header.into_bytes())
+        .collect::<Vec<Vec<u8>>>();
+
+    Ok(UpdateLightClientParams { chain_id, headers })
 }
 
-fn get_crr_locked_event_payload_params(
+pub(crate) fn get_crr_locked_event_payload_params(
     hex_data: HexString,
 ) -> Result<TxCrossChainRequestParams, Box<dyn StdError>> {
     let data = hex_data.to_vec();
@@ -955,7 +2257,109 @@ fn get_crr_locked_event_payload_params(
     })
 }
 
-fn get_crr_unlocked_event_payload_params(
+pub(crate) fn get_crr_message_event_payload_params(
+    hex_data: HexString,
+) -> Result<TxCrossChainMessageParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::String,    //src_chain_id
+            ParamType::String,    //dest_chain_id
+            ParamType::Address,   //src_contract
+            ParamType::Address,   //sender
+            ParamType::Address,   //handler
+            ParamType::Bytes,     //message
+            ParamType::Uint(256), //src_nonce
+            ParamType::Uint(64),  //src_block_number
+            ParamType::String,    //src_tx_hash
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // src_chain_id
+    let res = decoded_data[0].clone().into_string();
+    if res.is_none() {
+        return Err("src_chain_id is none".into());
+    }
+    let src_chain_id = res.unwrap();
+
+    // dst_chain_id
+    let res = decoded_data[1].clone().into_string();
+    if res.is_none() {
+        return Err("dst_chain_id is none".into());
+    }
+    let dst_chain_id = res.unwrap();
+
+    // src_contract
+    let res = decoded_data[2].clone().into_address();
+    if res.is_none() {
+        return Err("src_contract is none".into());
+    }
+    let src_contract = res.unwrap();
+
+    // sender
+    let res = decoded_data[3].clone().into_address();
+    if res.is_none() {
+        return Err("sender is none".into());
+    }
+    let sender = res.unwrap();
+
+    // handler
+    let res = decoded_data[4].clone().into_address();
+    if res.is_none() {
+        return Err("handler is none".into());
+    }
+    let handler = res.unwrap();
+
+    // message
+    let res = decoded_data[5].clone().into_bytes();
+    if res.is_none() {
+        return Err("message is none".into());
+    }
+    let message = res.unwrap();
+
+    // src_nonce
+    let res = decoded_data[6].clone().into_uint();
+    if res.is_none() {
+        return Err("src_nonce is none".into());
+    }
+    let src_nonce = uint_to_u128(res.unwrap(), "src_nonce")?;
+
+    // src_block_number
+    let res = decoded_data[7].clone().into_uint();
+    if res.is_none() {
+        return Err("src_block_number is none".into());
+    }
+    let src_block_number = uint_to_u64(res.unwrap(), "src_block_number")?;
+
+    // src_tx_hash
+    let res = decoded_data[8].clone().into_string();
+    if res.is_none() {
+        return Err("src_tx_hash is none".into());
+    }
+    let src_tx_hash = res.unwrap();
+
+    Ok(TxCrossChainMessageParams {
+        src_chain_id,
+        dst_chain_id,
+        src_contract: Address::from_slice(&src_contract.as_bytes()),
+        sender: Address::from_slice(&sender.as_bytes()),
+        handler: Address::from_slice(&handler.as_bytes()),
+        message: HexString::from_vec(message),
+        src_nonce,
+        src_block_number,
+        src_tx_hash,
+    })
+}
+
+pub(crate) fn get_crr_unlocked_event_payload_params(
     hex_data: HexString,
 ) -> Result<TxCrossChainReplyParams, Box<dyn StdError>> {
     let data = hex_data.to_vec();
@@ -1132,3 +2536,39 @@ fn get_update_tokens_payload(
 
     Ok(UpdateTokensPriceParams { tokens, prices })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::LocalWallet;
+
+    #[test]
+    fn keeps_proposer_signature_apart_from_votes() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let mut proposal = Proposal::new(
+            "digichain".to_string(),
+            ProposalType::UpdateRateLimit,
+            wallet.address(),
+            12u64,
+            HexString::from_vec(vec![1u8]),
+            Uint128::from(3u128),
+            0u64,
+            None,
+        );
+        let signature = proposal.sign(&wallet).unwrap();
+
+        let relayed = proposal.get_raw_proposal().to_proposal();
+        assert_eq!(relayed.signature, signature);
+        assert!(relayed.validtors_signature.is_empty());
+        assert!(!relayed.is_signed(wallet.address()));
+        assert_eq!(
+            relayed.get_raw_proposal().get_signer().unwrap(),
+            wallet.address()
+        );
+
+        // signature covers proposer and nonce
+        let mut raw_proposal = proposal.get_raw_proposal();
+        raw_proposal.nonce = Uint128::from(4u128);
+        assert_ne!(raw_proposal.get_signer().unwrap(), wallet.address());
+    }
+}
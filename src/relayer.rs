@@ -0,0 +1,645 @@
+use crate::{
+    constants::{
+        LockedFundRequest, RelayerConfirmations, RelayerMaxBlockRange, RelayerPollInterval,
+    },
+    crosschain::CrossChainRequestStatus,
+    digichain::DigiChain,
+    transaction::{Transaction, TxType},
+    types::{Address, HexString, TxExecutionResult},
+    utils::{decode_crosschain_request_type_data, encode_crosschain_request_type_data},
+};
+use cosmwasm_std::Uint128;
+use ethers::{
+    contract::{parse_log, EthEvent},
+    providers::Middleware,
+    types::{Filter, Log, Signature, U256 as EthU256},
+};
+use router_wasm_bindings::ethabi::{
+    encode, ethereum_types::U256, Address as EthRouterAddress, Token,
+};
+use std::{
+    collections::HashMap,
+    error::Error as StdError,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+// emitted by gateway when user locks funds to be minted on digichain
+#[derive(Clone, Debug, PartialEq, EthEvent)]
+#[ethevent(name = "LockedFund")]
+pub struct LockedFundEvent {
+    pub dst_chain_id: String,
+    pub depositor: Address,
+    pub recipient: Address,
+    pub tokens: Vec<Address>,
+    pub amounts: Vec<ethers::types::U256>,
+    pub nonce: ethers::types::U256, // gateway nonce, src_nonce of request
+}
+
+// emitted by gateway when withdraw msg signed by digichain validators is executed
+#[derive(Clone, Debug, PartialEq, EthEvent)]
+#[ethevent(name = "WithdrawExecuted")]
+pub struct WithdrawExecutedEvent {
+    pub request_type: u8,
+    pub src_chain_id: String,
+    pub src_nonce: ethers::types::U256,
+    pub depositor: Address,
+    pub recipient: Address,
+    pub tokens: Vec<Address>,
+    pub amounts: Vec<ethers::types::U256>,
+    pub nonce: ethers::types::U256, // gateway nonce, dst_nonce of request
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayerConfig {
+    pub poll_interval: u64,   // seconds between two polls of all chains
+    pub confirmations: u64,   // blocks behind head an event needs to be relayed
+    pub max_block_range: u64, // max blocks fetched in one get_logs call
+}
+
+impl Default for RelayerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: RelayerPollInterval,
+            confirmations: RelayerConfirmations,
+            max_block_range: RelayerMaxBlockRange,
+        }
+    }
+}
+
+// polls gateway events of registered chains and submits them as CrossChainRequest txs,
+// generic over middleware so it runs against any provider, anvil devnet or mocked one
+#[derive(Clone, Debug)]
+pub struct Relayer<M: Middleware> {
+    pub config: RelayerConfig,
+    pub providers: HashMap<String, Arc<M>>, // chain_id -> provider of that chain
+    pub cursors: HashMap<String, u64>,      // chain_id -> last block relayed by this node
+}
+
+impl<M: Middleware + 'static> Relayer<M> {
+    pub fn new(config: RelayerConfig) -> Relayer<M> {
+        Relayer {
+            config,
+            providers: HashMap::new(),
+            cursors: HashMap::new(),
+        }
+    }
+
+    pub fn add_provider(&mut self, chain_id: String, provider: Arc<M>) {
+        self.providers.insert(chain_id, provider);
+    }
+
+    pub async fn run(&mut self, digichain: DigiChain) {
+        loop {
+            if !digichain.pause.read().unwrap().clone() {
+                let chain_ids = self.providers.keys().cloned().collect::<Vec<String>>();
+                for chain_id in chain_ids {
+                    let res = self.poll(&digichain, chain_id.clone()).await;
+                    if res.is_err() {
+                        println!(
+                            "Relayer Poll Failed || ChainId: {}, Error: {:?}",
+                            chain_id,
+                            res.err()
+                        );
+                    }
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(self.config.poll_interval)).await;
+        }
+    }
+
+    // relays events of next block range of chain, returns submitted txs
+    pub async fn poll(
+        &mut self,
+        digichain: &DigiChain,
+        chain_id: String,
+    ) -> Result<Vec<Transaction>, Box<dyn StdError>> {
+        let res = self.providers.get(&chain_id);
+        if res.is_none() {
+            return Err(format!("no provider for chain {}", chain_id).into());
+        }
+        let provider = res.unwrap().clone();
+        let res = digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .get_contracts_config(vec![chain_id.clone()])
+            .remove(&chain_id);
+        if res.is_none() {
+            return Err(format!("contract config not found").into());
+        }
+        let contract_config = res.unwrap();
+        if contract_config.disabled {
+            return Ok(vec![]);
+        }
+        let res = Address::from_str(&contract_config.contract_address);
+        if res.is_err() {
+            return Err(format!("gateway address: {:?}", res.err()).into());
+        }
+        let gateway = res.unwrap();
+
+        // resume from whichever is ahead, blocks processed by chain or relayed by this node
+        let cursor = self
+            .cursors
+            .get(&chain_id)
+            .cloned()
+            .unwrap_or(0u64)
+            .max(contract_config.last_processed_block);
+        let from_block = (cursor + 1u64).max(contract_config.start_block);
+        let res = provider.get_block_number().await;
+        if res.is_err() {
+            return Err(format!("get_block_number: {:?}", res.err()).into());
+        }
+        let head = res.unwrap().as_u64();
+        if head < self.config.confirmations {
+            return Ok(vec![]);
+        }
+        let to_block = (head - self.config.confirmations)
+            .min(from_block + self.config.max_block_range.max(1u64) - 1u64);
+        if from_block > to_block {
+            return Ok(vec![]);
+        }
+
+        let filter = Filter::new()
+            .address(gateway)
+            .from_block(from_block)
+            .to_block(to_block)
+            .topic0(vec![
+                LockedFundEvent::signature(),
+                WithdrawExecutedEvent::signature(),
+            ]);
+        let res = provider.get_logs(&filter).await;
+        if res.is_err() {
+            return Err(format!("get_logs: {:?}", res.err()).into());
+        }
+        let logs = res.unwrap();
+
+        let mut txs: Vec<Transaction> = Vec::new();
+        let mut relayed_block = to_block;
+        for log in logs {
+            let log_block = log.block_number.map_or(0u64, |number| number.as_u64());
+            let res = self.get_transaction(digichain, chain_id.clone(), gateway, log);
+            if res.is_err() {
+                // malformed event can't be relayed later either
+                println!(
+                    "Relayer Skipped Event || ChainId: {}, Error: {:?}",
+                    chain_id,
+                    res.err()
+                );
+                continue;
+            }
+            let tx = res.unwrap();
+            let res = submit_transaction(digichain, &tx);
+            if res.is_err() {
+                // retried in next poll from block of this event, events relayed before it are skipped then
+                println!(
+                    "Relayer Submit Failed || ChainId: {}, TxHash: {}, Error: {:?}",
+                    chain_id,
+                    tx.hash,
+                    res.err()
+                );
+                relayed_block = log_block.saturating_sub(1u64);
+                break;
+            }
+            if !res.unwrap() {
+                // relayed already, by this node in earlier poll or by other validator
+                continue;
+            }
+            txs.push(tx);
+        }
+        self.cursors.insert(chain_id.clone(), relayed_block);
+        println!(
+            "Relayed Events || ChainId: {}, FromBlock: {}, ToBlock: {}, Txs: {}",
+            chain_id,
+            from_block,
+            relayed_block,
+            txs.len()
+        );
+        Ok(txs)
+    }
+
+    // builds signed CrossChainRequest tx of gateway event
+    fn get_transaction(
+        &self,
+        digichain: &DigiChain,
+        chain_id: String,
+        gateway: Address,
+        log: Log,
+    ) -> Result<Transaction, Box<dyn StdError>> {
+        let from = digichain.validator.read().unwrap().acccount.address;
+        let block_number = log.block_number.map_or(0u64, |number| number.as_u64());
+        let tx_hash = log
+            .transaction_hash
+            .map_or(String::new(), |hash| format!("{:?}", hash));
+        let res = log.topics.first().cloned();
+        if res.is_none() {
+            return Err(format!("log without topic").into());
+        }
+        let topic = res.unwrap();
+
+        let request_type_data: HexString;
+        let data: Vec<u8>;
+        if topic == LockedFundEvent::signature() {
+            let res = parse_log::<LockedFundEvent>(log);
+            if res.is_err() {
+                return Err(format!("LockedFund: {:?}", res.err()).into());
+            }
+            let event = res.unwrap();
+            let res = u128::try_from(event.nonce);
+            if res.is_err() {
+                return Err(format!("LockedFund: nonce out of range").into());
+            }
+            request_type_data = encode_crosschain_request_type_data(
+                LockedFundRequest,
+                chain_id.clone(),
+                Uint128::from(res.unwrap()),
+                event.dst_chain_id.clone(),
+                Uint128::zero(),
+                from,
+            );
+            data = encode(&vec![
+                Token::String(chain_id),
+                Token::String(event.dst_chain_id),
+                address_token(gateway),
+                address_token(event.recipient),
+                address_token(event.depositor),
+                Token::Array(event.tokens.into_iter().map(address_token).collect()),
+                Token::Array(event.amounts.into_iter().map(uint_token).collect()),
+                uint_token(event.nonce),
+                Token::Uint(U256::from(block_number)),
+                Token::String(tx_hash),
+            ]);
+        } else if topic == WithdrawExecutedEvent::signature() {
+            let res = parse_log::<WithdrawExecutedEvent>(log);
+            if res.is_err() {
+                return Err(format!("WithdrawExecuted: {:?}", res.err()).into());
+            }
+            let event = res.unwrap();
+            let src_nonce = u128::try_from(event.src_nonce);
+            let dst_nonce = u128::try_from(event.nonce);
+            if src_nonce.is_err() || dst_nonce.is_err() {
+                return Err(format!("WithdrawExecuted: nonce out of range").into());
+            }
+            let src_nonce = Uint128::from(src_nonce.unwrap());
+            let dst_nonce = Uint128::from(dst_nonce.unwrap());
+            let mut amounts: Vec<Uint128> = Vec::new();
+            for amount in event.amounts.iter() {
+                let res = u128::try_from(*amount);
+                if res.is_err() {
+                    return Err(format!("WithdrawExecuted: amount out of range").into());
+                }
+                amounts.push(Uint128::from(res.unwrap()));
+            }
+            request_type_data = encode_crosschain_request_type_data(
+                event.request_type,
+                event.src_chain_id.clone(),
+                src_nonce,
+                chain_id.clone(),
+                dst_nonce,
+                from,
+            );
+            data = encode_withdraw_executed_data(
+                event.src_chain_id,
+                chain_id,
+                gateway,
+                event.recipient,
+                event.depositor,
+                event.tokens,
+                amounts,
+                src_nonce,
+                dst_nonce,
+                block_number,
+                tx_hash,
+            );
+        } else {
+            return Err(format!("unknown event {:?}", topic).into());
+        }
+        get_signed_transaction(
+            digichain,
+            TxType::CrossChainRequest(request_type_data),
+            data,
+        )
+    }
+}
+
+// tx signed by validator of this node, nonce follows its txs waiting in mempool,
+// so tx has to be submitted before next one is built
+pub(crate) fn get_signed_transaction(
+    digichain: &DigiChain,
+    tx_type: TxType,
+    data: Vec<u8>,
+) -> Result<Transaction, Box<dyn StdError>> {
+    let validator = digichain.validator.read().unwrap().clone();
+    let from = validator.acccount.address;
+    let nonce = digichain.get_pending_tx_nonce(from);
+    let created_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut tx = Transaction {
+        hash: String::new(),
+        created_at,
+        chain_id: digichain.chain_id.clone(),
+        timestamp: 0u64,
+        nonce,
+        from,
+        data: HexString::from_vec(data),
+        signature: Signature {
+            r: EthU256::zero(),
+            s: EthU256::zero(),
+            v: 0u64,
+        },
+        block_number: 0u64,
+        result: TxExecutionResult::None,
+        tx_type,
+        proof: None,
+    };
+    tx.sign(&validator.wallet)?;
+    Ok(tx)
+}
+
+// data of reply CrossChainRequest tx, decoded by get_crr_unlocked_event_payload_params
+pub(crate) fn encode_withdraw_executed_data(
+    src_chain_id: String,
+    dst_chain_id: String,
+    gateway: Address,
+    recipient: Address,
+    depositor: Address,
+    tokens: Vec<Address>,
+    amounts: Vec<Uint128>,
+    src_nonce: Uint128,
+    dst_nonce: Uint128,
+    dst_block_number: u64,
+    dst_tx_hash: String,
+) -> Vec<u8> {
+    encode(&vec![
+        Token::String(src_chain_id),
+        Token::String(dst_chain_id),
+        address_token(gateway),
+        address_token(recipient),
+        address_token(depositor),
+        Token::Array(tokens.into_iter().map(address_token).collect()),
+        Token::Array(
+            amounts
+                .into_iter()
+                .map(|amount| Token::Uint(U256::from(amount.u128())))
+                .collect(),
+        ),
+        Token::Uint(U256::from(src_nonce.u128())),
+        Token::Uint(U256::from(dst_nonce.u128())),
+        Token::Uint(U256::from(dst_block_number)),
+        Token::String(dst_tx_hash),
+    ])
+}
+
+// same as broadcast_transaction rpc, request is marked broadcasted by this validator once tx is in mempool,
+// false if request got relayed already
+fn submit_transaction(digichain: &DigiChain, tx: &Transaction) -> Result<bool, Box<dyn StdError>> {
+    let account_nonce = digichain
+        .get_account(tx.from)
+        .map_or(Uint128::zero(), |account| account.tx_nonce);
+    digichain
+        .mempool
+        .read()
+        .unwrap()
+        .check_tx_nonce(tx, account_nonce)?;
+    let mut info = None;
+    if let TxType::CrossChainRequest(request_type_data) = &tx.tx_type {
+        let res = decode_crosschain_request_type_data(request_type_data);
+        if res.is_err() {
+            return Err(format!("{:?}", res.err()).into());
+        }
+        let res = res.unwrap();
+        if digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .is_broadcasted_by_any(res.src_chain_id.clone(), res.src_nonce)
+        {
+            return Ok(false);
+        }
+        info = Some(res);
+    }
+    digichain
+        .mempool
+        .write()
+        .unwrap()
+        .add_transaction(tx, account_nonce)?;
+    if let Some(info) = info {
+        let block_number = digichain.get_block_number();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        let res = crosschain.broadcasted(tx.from, info.src_chain_id.clone(), info.src_nonce);
+        if res.is_err() {
+            return Err(res.err().unwrap());
+        }
+        crosschain.update_request_status(
+            info.src_chain_id,
+            info.src_nonce,
+            CrossChainRequestStatus::Broadcasted,
+            block_number,
+        );
+    }
+    Ok(true)
+}
+
+fn address_token(address: Address) -> Token {
+    Token::Address(EthRouterAddress::from_slice(address.as_bytes()))
+}
+
+// full 256 bits are kept, amounts beyond u128 get rejected while executing request
+fn uint_token(value: ethers::types::U256) -> Token {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    Token::Uint(U256::from_big_endian(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ChainTypeEvm;
+    use ethers::{
+        abi::{encode as eencode, Token as EthToken},
+        providers::{MockProvider, Provider},
+        signers::Signer,
+        types::{Bytes, H256, U64},
+    };
+
+    // chain with ethereum gateway registered, validator of this node relays its events
+    fn get_digichain() -> DigiChain {
+        let mut digichain = DigiChain::default();
+        digichain.chain_id = "digichain".to_string();
+        {
+            let mut validator = digichain.validator.write().unwrap();
+            validator.acccount.address = validator.wallet.address();
+        }
+        digichain
+            .crosschain
+            .write()
+            .unwrap()
+            .add_contract_config(
+                "ethereum".to_string(),
+                format!("{:?}", get_gateway()),
+                0u64,
+                ChainTypeEvm,
+            )
+            .unwrap();
+        digichain
+    }
+
+    fn get_gateway() -> Address {
+        Address::from_low_u64_be(2u64)
+    }
+
+    fn get_relayer() -> (Relayer<Provider<MockProvider>>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let mut relayer = Relayer::new(RelayerConfig {
+            poll_interval: 1u64,
+            confirmations: 1u64,
+            max_block_range: 100u64,
+        });
+        relayer.add_provider("ethereum".to_string(), Arc::new(provider));
+        (relayer, mock)
+    }
+
+    fn get_locked_fund_log(nonce: u64, block_number: u64) -> Log {
+        Log {
+            address: get_gateway(),
+            topics: vec![LockedFundEvent::signature()],
+            data: Bytes::from(eencode(&[
+                EthToken::String("digichain".to_string()),
+                EthToken::Address(Address::from_low_u64_be(4u64)),
+                EthToken::Address(Address::from_low_u64_be(5u64)),
+                EthToken::Array(vec![EthToken::Address(Address::from_low_u64_be(3u64))]),
+                EthToken::Array(vec![EthToken::Uint(ethers::types::U256::from(100u64))]),
+                EthToken::Uint(ethers::types::U256::from(nonce)),
+            ])),
+            block_number: Some(U64::from(block_number)),
+            transaction_hash: Some(H256::from_low_u64_be(nonce)),
+            ..Default::default()
+        }
+    }
+
+    // responses are popped from back, block number is requested before logs
+    fn push_poll_responses(mock: &MockProvider, head: u64, logs: Vec<Log>) {
+        mock.push::<Vec<Log>, _>(logs).unwrap();
+        mock.push(U64::from(head)).unwrap();
+    }
+
+    #[actix_web::test]
+    async fn submits_locked_fund_event() {
+        let digichain = get_digichain();
+        let validator = digichain.validator.read().unwrap().acccount.address;
+        let (mut relayer, mock) = get_relayer();
+        push_poll_responses(&mock, 10u64, vec![get_locked_fund_log(1u64, 5u64)]);
+
+        let txs = relayer
+            .poll(&digichain, "ethereum".to_string())
+            .await
+            .unwrap();
+        assert_eq!(txs.len(), 1usize);
+        assert_eq!(txs[0].from, validator);
+        let info = match &txs[0].tx_type {
+            TxType::CrossChainRequest(data) => decode_crosschain_request_type_data(data).unwrap(),
+            _ => panic!("relayed tx is not a CrossChainRequest tx"),
+        };
+        assert_eq!(info.request_type, LockedFundRequest);
+        assert_eq!(info.src_chain_id, "ethereum".to_string());
+        assert_eq!(info.src_nonce, Uint128::from(1u128));
+        assert_eq!(info.dst_chain_id, "digichain".to_string());
+        assert_eq!(digichain.mempool.read().unwrap().transactions.len(), 1usize);
+        assert!(digichain.crosschain.read().unwrap().is_broadcasted(
+            validator,
+            "ethereum".to_string(),
+            Uint128::from(1u128)
+        ));
+        assert_eq!(relayer.cursors.get("ethereum"), Some(&9u64));
+    }
+
+    #[actix_web::test]
+    async fn skips_event_relayed_by_other_validator() {
+        let digichain = get_digichain();
+        digichain
+            .crosschain
+            .write()
+            .unwrap()
+            .broadcasted(
+                Address::from_low_u64_be(9u64),
+                "ethereum".to_string(),
+                Uint128::from(1u128),
+            )
+            .unwrap();
+        let (mut relayer, mock) = get_relayer();
+        push_poll_responses(&mock, 10u64, vec![get_locked_fund_log(1u64, 5u64)]);
+
+        let txs = relayer
+            .poll(&digichain, "ethereum".to_string())
+            .await
+            .unwrap();
+        assert!(txs.is_empty());
+        assert!(digichain.mempool.read().unwrap().transactions.is_empty());
+        // nothing left to relay in range
+        assert_eq!(relayer.cursors.get("ethereum"), Some(&9u64));
+    }
+
+    #[actix_web::test]
+    async fn skips_malformed_event_and_retries_failed_submission() {
+        let digichain = get_digichain();
+        let validator = digichain.validator.read().unwrap().acccount.address;
+        let (mut relayer, mock) = get_relayer();
+
+        // tx waiting in queue with nonce relayed tx gets, mempool rejects it as duplicate
+        let blocking_tx = get_signed_transaction(
+            &digichain,
+            TxType::CrossChainRequest(HexString::from_vec(vec![])),
+            vec![],
+        )
+        .unwrap();
+        digichain
+            .mempool
+            .write()
+            .unwrap()
+            .queued_transactions
+            .entry(validator)
+            .or_default()
+            .insert(blocking_tx.nonce, blocking_tx);
+        let mut malformed_log = get_locked_fund_log(1u64, 4u64);
+        malformed_log.data = Bytes::from(vec![1u8; 5]);
+        push_poll_responses(
+            &mock,
+            10u64,
+            vec![malformed_log, get_locked_fund_log(1u64, 5u64)],
+        );
+        let txs = relayer
+            .poll(&digichain, "ethereum".to_string())
+            .await
+            .unwrap();
+        assert!(txs.is_empty());
+        assert!(!digichain.crosschain.read().unwrap().is_broadcasted(
+            validator,
+            "ethereum".to_string(),
+            Uint128::from(1u128)
+        ));
+        // next poll starts from block of failed event
+        assert_eq!(relayer.cursors.get("ethereum"), Some(&4u64));
+
+        digichain
+            .mempool
+            .write()
+            .unwrap()
+            .queued_transactions
+            .clear();
+        push_poll_responses(&mock, 10u64, vec![get_locked_fund_log(1u64, 5u64)]);
+        let txs = relayer
+            .poll(&digichain, "ethereum".to_string())
+            .await
+            .unwrap();
+        assert_eq!(txs.len(), 1usize);
+        assert!(digichain.crosschain.read().unwrap().is_broadcasted(
+            validator,
+            "ethereum".to_string(),
+            Uint128::from(1u128)
+        ));
+        assert_eq!(relayer.cursors.get("ethereum"), Some(&9u64));
+    }
+}
@@ -12,7 +12,7 @@ use router_wasm_bindings::ethabi::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error as StdError, str::FromStr};
+use std::{collections::HashMap, error::Error as StdError};
 
 pub const ALPHA_KEY: [char; 62] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i',
@@ -28,6 +28,8 @@ pub struct DigiToken {
     pub symbol: String,
     pub decimal: u8,
     pub chain_token_mapping: HashMap<String, String>, // chain_id : token_address on chain with chainid chain_id
+    #[serde(default)]
+    pub chain_decimals: HashMap<String, u8>, // chain_id : token decimal on chain, defaults to decimal
     pub price: Uint128,
     pub balance_mp: HashMap<Address, Uint128>,
 }
@@ -39,6 +41,7 @@ impl DigiToken {
         decimal: u8,
         price: Uint128,
         chain_token_mapping: HashMap<String, String>,
+        chain_decimals: HashMap<String, u8>,
     ) -> DigiToken {
         //TODO: considering this will generate unique id
         let id = nanoid!(40, &ALPHA_KEY);
@@ -49,6 +52,7 @@ impl DigiToken {
             price,
             symbol,
             chain_token_mapping,
+            chain_decimals,
             balance_mp: HashMap::new(),
         }
     }
@@ -68,6 +72,20 @@ impl DigiToken {
         true
     }
 
+    pub fn burn(&mut self, from: Address, amount: Uint128) -> Result<(), Box<dyn StdError>> {
+        let balance = self.get_balance_of(from);
+        if balance < amount {
+            return Err(format!(
+                "[{}]: insufficient fund, balance: {}",
+                self.id.clone(),
+                balance
+            )
+            .into());
+        }
+        self.balance_mp.insert(from, balance - amount);
+        Ok(())
+    }
+
     pub fn get_token_id(self) -> String {
         return self.id;
     }
@@ -89,6 +107,28 @@ impl DigiToken {
         self.chain_token_mapping
             .insert(chain_id, token.to_lowercase());
     }
+    pub fn get_chain_decimal(&self, chain_id: String) -> u8 {
+        *self.chain_decimals.get(&chain_id).unwrap_or(&self.decimal)
+    }
+
+    // converts digichain amount to amount in token decimals on chain, rejects dust
+    pub fn to_chain_amount(
+        &self,
+        chain_id: String,
+        amount: Uint128,
+    ) -> Result<Uint128, Box<dyn StdError>> {
+        scale_amount(amount, self.decimal, self.get_chain_decimal(chain_id))
+    }
+
+    // converts amount in token decimals on chain to digichain amount, rejects dust
+    pub fn from_chain_amount(
+        &self,
+        chain_id: String,
+        amount: Uint128,
+    ) -> Result<Uint128, Box<dyn StdError>> {
+        scale_amount(amount, self.get_chain_decimal(chain_id), self.decimal)
+    }
+
     pub fn update_token_price(&mut self, price: Uint128) {
         //TODO: will be added after consensus only
         self.price = price;
@@ -157,7 +197,19 @@ impl DigiToken {
         if res.is_none() {
             return Err(format!("no token found on dst chain").into());
         }
+        // token address in format of dst chain
+        let res = crosschain
+            .get_chain_adapter(dst_chain_id.clone())
+            .and_then(|adapter| adapter.parse_address(res.unwrap()));
+        if res.is_err() {
+            return Err(res.err().unwrap());
+        }
         let dst_token_address = res.unwrap();
+        let res = self.to_chain_amount(dst_chain_id.clone(), amount);
+        if res.is_err() {
+            return Err(res.err().unwrap());
+        }
+        let dst_amount = res.unwrap();
 
         //update sender and recipient balance
         // decrease sender balance by amount
@@ -175,8 +227,9 @@ impl DigiToken {
         self.balance_mp.insert(from, sender_balance);
 
         let dst_token_address_token =
-            Token::Address(EthRouterAddress::from_str(&dst_token_address).unwrap());
-        Ok(encode(&vec![dst_token_address_token]))
+            Token::Address(EthRouterAddress::from_slice(dst_token_address.as_bytes()));
+        let dst_amount_token = Token::Uint(U256::from(dst_amount.u128()));
+        Ok(encode(&vec![dst_token_address_token, dst_amount_token]))
     }
 
     pub fn execute(
@@ -196,6 +249,36 @@ impl DigiToken {
     }
 }
 
+fn scale_amount(amount: Uint128, from: u8, to: u8) -> Result<Uint128, Box<dyn StdError>> {
+    if from == to {
+        return Ok(amount);
+    }
+    if to > from {
+        let factor = 10u128.checked_pow((to - from) as u32);
+        if factor.is_none() {
+            return Err(format!("decimal difference too large: {} -> {}", from, to).into());
+        }
+        let res = amount.checked_mul(Uint128::from(factor.unwrap()));
+        if res.is_err() {
+            return Err(format!("amount overflow: {:?}", res.err()).into());
+        }
+        return Ok(res.unwrap());
+    }
+    let factor = 10u128.checked_pow((from - to) as u32);
+    if factor.is_none() {
+        return Err(format!("decimal difference too large: {} -> {}", from, to).into());
+    }
+    let factor = Uint128::from(factor.unwrap());
+    if !(amount % factor).is_zero() {
+        return Err(format!(
+            "amount {} loses precision converting {} to {} decimals",
+            amount, from, to
+        )
+        .into());
+    }
+    Ok(amount / factor)
+}
+
 fn decode_tx_data(data: Vec<u8>) -> Result<(Address, Uint128), Box<dyn StdError>> {
     let res = decode(&[ParamType::Address, ParamType::Uint(256)], &data);
     if res.is_err() {
@@ -1,31 +1,53 @@
 use crate::{
-    constants::UnLockedWithdrawRequest,
-    crosschain,
+    constants::{MessageRequest, UnLockedWithdrawRequest},
+    crosschain::{
+        self, CrossChainExecutionResult, CrossChainFee, CrossChainRequestStatus, ReserveFlow,
+    },
     digichain::DigiChain,
+    eip712::{get_tx_typed_data, get_typed_data_digest},
+    light_client::ReceiptProof,
     proposal::{CrossChainWithdrawMsg, ExtraData, Proposal, ProposalType},
     token::{decode_crosschain_tx_data, DigiToken},
-    types::{Address, HexString, TokenAcceptsParams, TxExecutionResult},
+    types::{
+        Address, CrossChainWithdrawPayload, FeeConfig, HexString, TokenAcceptsParams, TokenId,
+        TxExecutionResult,
+    },
     utils::{
-        decode_crosschain_request_type_data, get_crosschain_transfer_payload_params,
-        get_crosschain_withdraw_payload, get_transfer_payload_params, is_within_slippage,
+        decode_crosschain_request_type_data, get_crosschain_message_payload_params,
+        get_crosschain_transfer_payload_params, get_transfer_payload_params, is_within_slippage,
     },
 };
 use cosmwasm_std::Uint128;
-use ethers::types::Signature;
+use ethers::{
+    core::k256::ecdsa::SigningKey,
+    signers::{Signer, Wallet},
+    types::{Signature, H256},
+    utils::hash_message,
+};
 use router_wasm_bindings::ethabi::{
-    decode, encode, Address as EthRouterAddress, Error as EthError, ParamType, Token,
+    decode, encode, ethereum_types::U256, Address as EthRouterAddress, Error as EthError,
+    ParamType, Token,
 };
 use serde::{Deserialize, Deserializer, Serialize};
-use std::error::Error as StdError;
+use std::{collections::HashMap, error::Error as StdError};
 
 #[derive(Serialize, Clone, Debug, PartialEq)]
 pub enum TxType {
     Transfer,
     CrosschainTransfer(String), // dst chain [created from this to other chain] and recipient on that chain
     CrossChainRequest(HexString), // src_chain,src_nonce, dst_chainId, validator assigned to execute this
+    CrossChainMessage(String),    // dst chain, message to contract on that chain
     UserKYC,
     None,
     AddContractConfig,
+    UpdateContractConfig,
+    DisableChain,
+    RemoveContractConfig,
+    UpdateRateLimit,
+    RegisterMessageHandler,
+    UpdateFeeConfig,
+    UpdateBatchConfig,
+    UpdateLightClient,
     AddToken,
     UpdateTokenAccepts,
     UpdateTokensPrice,
@@ -47,9 +69,70 @@ pub struct RawTransaction {
     pub signature: Signature,
     pub chain_id: String,
     pub data: HexString,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<ReceiptProof>, // src chain receipt proof of inbound crosschain request
+}
+
+// fields of tx covered by signature of sender, everything except signature and hash
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TxSigningPayload {
+    pub chain_id: String,
+    pub created_at: u64,
+    pub nonce: Uint128,
+    pub from: Address,
+    pub tx_type: TxType,
+    pub data: HexString,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<ReceiptProof>,
 }
 
 impl RawTransaction {
+    pub fn get_signing_fields(&self) -> TxSigningPayload {
+        TxSigningPayload {
+            chain_id: self.chain_id.clone(),
+            created_at: self.created_at,
+            nonce: self.nonce,
+            from: self.from,
+            tx_type: self.tx_type.clone(),
+            data: self.data.clone(),
+            proof: self.proof.clone(),
+        }
+    }
+
+    // json of signing payload, sender signs it as personal message (eip-191)
+    pub fn get_signing_payload(&self) -> Vec<u8> {
+        serde_json::to_vec(&self.get_signing_fields()).expect("Serialization failed")
+    }
+
+    pub fn get_signing_digest(&self) -> H256 {
+        hash_message(self.get_signing_payload())
+    }
+
+    // signer recovered from signature has to be sender of tx, signature can be of
+    // - personal message (eip-191) of json signing payload, signed by nodes and cli wallets
+    // - eip-712 typed data of same fields, signed by browser wallets which show decoded payload
+    // both digests commit to every signing field, their prefixes (0x1945, 0x1901) keep them apart
+    pub fn verify_signature(&self) -> Result<(), Box<dyn StdError>> {
+        let res = self.signature.recover(self.get_signing_digest());
+        if res.is_ok() && res.unwrap() == self.from {
+            return Ok(());
+        }
+        let res = get_tx_typed_data(&self.get_signing_fields())
+            .and_then(|typed_data| get_typed_data_digest(&typed_data));
+        if res.is_err() {
+            return Err(format!("tx not signed by sender {:?}", self.from).into());
+        }
+        let res = self.signature.recover(res.unwrap());
+        if res.is_err() {
+            return Err(format!("invalid signature: {:?}", res.err()).into());
+        }
+        let signer = res.unwrap();
+        if signer != self.from {
+            return Err(format!("tx signed by {:?}, not by sender {:?}", signer, self.from).into());
+        }
+        Ok(())
+    }
+
     pub fn to_transaction(self) -> Transaction {
         Transaction {
             chain_id: self.chain_id,
@@ -63,6 +146,7 @@ impl RawTransaction {
             block_number: 0,
             result: TxExecutionResult::None,
             tx_type: self.tx_type,
+            proof: self.proof,
         }
     }
 }
@@ -80,6 +164,8 @@ pub struct Transaction {
     pub block_number: u64,
     pub result: TxExecutionResult,
     pub tx_type: TxType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<ReceiptProof>, // src chain receipt proof of inbound crosschain request
 }
 
 impl Transaction {
@@ -106,6 +192,7 @@ impl Transaction {
             signature,
             block_number: 0u64,
             result: TxExecutionResult::None,
+            proof: None,
         }
     }
 
@@ -124,9 +211,24 @@ impl Transaction {
             data: self.data.clone(),
             signature: self.signature,
             tx_type: self.tx_type.clone(),
+            proof: self.proof.clone(),
         }
     }
 
+    // signs tx with sender wallet and sets its hash
+    pub fn sign(&mut self, wallet: &Wallet<SigningKey>) -> Result<(), Box<dyn StdError>> {
+        if wallet.address() != self.from {
+            return Err(format!("wallet {:?} is not sender of tx", wallet.address()).into());
+        }
+        let res = wallet.sign_hash(self.get_raw_tx().get_signing_digest());
+        if res.is_err() {
+            return Err(format!("signing: {:?}", res.err()).into());
+        }
+        self.signature = res.unwrap();
+        self.hash = self.calculate_hash();
+        Ok(())
+    }
+
     pub fn is_valid(&self, digichain: DigiChain) -> bool {
         //Required: data and to tokens length  same
         // if self.data.len() != self.tokens.len() {
@@ -142,24 +244,10 @@ impl Transaction {
         if self.chain_id != digichain.chain_id {
             return false;
         }
-        //TODO: is this tx is signed by from user or not
-        // let msg_bytes = self.get_raw_tx().to_byte();
-        // if msg_bytes.is_err() {
-        //     return false;
-        // }
-        // let msg_bytes = msg_bytes.unwrap();
-        // let res = self.signature.recover(msg_bytes);
-        // if res.is_err() {
-        //     return false;
-        // }
-        // let original_signer = res.unwrap();
-        // if original_signer != self.from {
-        //     return false;
-        // }
-
-        // let res = ethers::abi::Hash::from_slice(&msg_bytes);
-        // if ethers::abi::Hash::from_slice(&msg_bytes) {
-        // }
+        // tx has to be signed by from user
+        if self.get_raw_tx().verify_signature().is_err() {
+            return false;
+        }
         true
     }
 
@@ -178,6 +266,12 @@ impl Transaction {
                 timestamp,
                 digichain,
             ),
+            TxType::CrossChainMessage(dst_chain_id) => self.crosschain_send_message(
+                dst_chain_id.clone(),
+                block_number,
+                timestamp,
+                digichain,
+            ),
             TxType::UserKYC => self.user_kyc(block_number, timestamp, digichain),
             TxType::CrossChainRequest(data) => {
                 self.add_cross_chain_request(&data.clone(), block_number, timestamp, digichain)
@@ -186,6 +280,54 @@ impl Transaction {
             TxType::AddContractConfig => {
                 self.add_contract_config(block_number, timestamp, digichain)
             }
+            TxType::UpdateContractConfig => self.propose_governance_change(
+                ProposalType::UpdateContractConfig,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::DisableChain => self.propose_governance_change(
+                ProposalType::DisableChain,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::RemoveContractConfig => self.propose_governance_change(
+                ProposalType::RemoveContractConfig,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::UpdateRateLimit => self.propose_governance_change(
+                ProposalType::UpdateRateLimit,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::RegisterMessageHandler => self.propose_governance_change(
+                ProposalType::RegisterMessageHandler,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::UpdateFeeConfig => self.propose_governance_change(
+                ProposalType::UpdateFeeConfig,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::UpdateBatchConfig => self.propose_governance_change(
+                ProposalType::UpdateBatchConfig,
+                block_number,
+                timestamp,
+                digichain,
+            ),
+            TxType::UpdateLightClient => self.propose_governance_change(
+                ProposalType::UpdateLightClient,
+                block_number,
+                timestamp,
+                digichain,
+            ),
             TxType::UpdateTokenAccepts => {
                 self.update_token_accepts(block_number, timestamp, digichain)
             }
@@ -324,14 +466,28 @@ impl Transaction {
             return Err(format!("decoding data: {:?}", params.err()).into());
         }
         let params: crate::types::TxCrossChainTransferParams = params.unwrap();
+        if digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .is_chain_disabled(dst_chain_id.clone())
+        {
+            return Err(format!("chain {} is disabled", dst_chain_id).into());
+        }
+
+        let fee_config = digichain
+            .crosschain
+            .read()
+            .unwrap()
+            .get_fee_config(dst_chain_id.clone());
 
         let tokens = params.tokens;
         let data = params.data;
+        let mut fees: Vec<CrossChainFee> = Vec::new();
         for idx in 0..tokens.len() {
             // check if token exist
             let to_token = tokens[idx].clone();
-            let tres = token_list.get_mut(&to_token);
-            if tres.is_none() {
+            if !token_list.contains_key(&to_token) {
                 return Err(format!("token not found {:?}", to_token).into());
             }
             let data = data[idx].to_vec();
@@ -339,13 +495,69 @@ impl Transaction {
                 return Err(format!("{:?}", data.err()).into());
             }
             let data = data.unwrap();
+            let amount = decode_crosschain_tx_data(data.clone());
+            if amount.is_err() {
+                return Err(format!("{:?}", amount.err()).into());
+            }
+            let mut amount = amount.unwrap();
+            // validator executing it on dst chain gets fee once ack arrives
+            let mut fee: Option<CrossChainFee> = None;
+            if let Some(config) = &fee_config {
+                let res = get_transfer_fee(config, &token_list, to_token.clone(), amount);
+                if res.is_err() {
+                    return Err(format!("{:?}", res.err()).into());
+                }
+                let (fee_token_id, fee_amount) = res.unwrap();
+                if fee_token_id == to_token {
+                    if fee_amount >= amount {
+                        return Err(format!(
+                            "amount {} doesn't cover fee {} for {:?}",
+                            amount, fee_amount, to_token
+                        )
+                        .into());
+                    }
+                    // fee is taken out of bridged amount
+                    amount = amount - fee_amount;
+                }
+                if !fee_amount.is_zero() {
+                    fee = Some(CrossChainFee {
+                        token_id: fee_token_id,
+                        amount: fee_amount,
+                        paid_by: self.from,
+                        paid_to: None,
+                        refunded: false,
+                    });
+                }
+            }
+            // withdraws over cap are rejected, nothing is burned yet
+            let res = digichain.crosschain.read().unwrap().check_rate_limit(
+                dst_chain_id.clone(),
+                to_token.clone(),
+                self.from,
+                amount,
+                block_number,
+            );
+            if res.is_err() {
+                return Err(format!("{:?}", res.err()).into());
+            }
+            if let Some(fee) = fee {
+                // escrowed by burning, minted to validator on ack
+                let res = token_list
+                    .get_mut(&fee.token_id)
+                    .unwrap()
+                    .burn(self.from, fee.amount);
+                if res.is_err() {
+                    return Err(format!("fee: {:?}", res.err()).into());
+                }
+                fees.push(fee);
+            }
             // token exist
-            let ttoken: &mut DigiToken = tres.unwrap();
+            let ttoken: &mut DigiToken = token_list.get_mut(&to_token).unwrap();
 
             let res = ttoken.execute(
                 self.tx_type.clone(),
                 self.from.clone(),
-                data.clone(),
+                encode(&vec![Token::Uint(U256::from(amount.u128()))]),
                 Some(&mut digichain.crosschain.write().unwrap()),
             );
             if res.is_err() {
@@ -354,7 +566,10 @@ impl Transaction {
                 );
             }
             let tx_execution_result = res.unwrap();
-            let res = decode(&vec![ParamType::Address], &tx_execution_result);
+            let res = decode(
+                &vec![ParamType::Address, ParamType::Uint(256)],
+                &tx_execution_result,
+            );
             if res.is_err() {
                 return Err(format!(
                     "{:?}",
@@ -363,29 +578,161 @@ impl Transaction {
                 )
                 .into());
             }
-            let res = res.unwrap();
-            let res = res[0].clone().into_address();
+            let decoded = res.unwrap();
+            let res = decoded[0].clone().into_address();
+            if res.is_none() {
+                return Err(format!("UnknownError").into());
+            }
+            let dst_token = Address::from_slice(&res.unwrap().as_bytes().to_vec());
+            let res = decoded[1].clone().into_uint();
             if res.is_none() {
                 return Err(format!("UnknownError").into());
             }
 
-            dst_tokens.push(Address::from_slice(&res.unwrap().as_bytes().to_vec()));
-            let res = decode_crosschain_tx_data(data);
-            dst_amounts.push(res.unwrap());
+            dst_tokens.push(dst_token);
+            // amount in token decimals on dst chain
+            dst_amounts.push(Uint128::from(res.unwrap().as_u128()));
+            let mut crosschain = digichain.crosschain.write().unwrap();
+            crosschain.record_rate_limit_usage(
+                dst_chain_id.clone(),
+                to_token.clone(),
+                self.from,
+                amount,
+                block_number,
+            );
+            let res = crosschain.record_reserve_flow(
+                dst_chain_id.clone(),
+                to_token,
+                ReserveFlow::Burned,
+                amount,
+            );
+            if res.is_err() {
+                return Err(format!("{:?}", res.err()).into());
+            }
         }
 
         let mut crosschain = digichain.crosschain.write().unwrap();
+        let res = crosschain
+            .get_chain_adapter(dst_chain_id.clone())
+            .and_then(|adapter| {
+                let payload = adapter.encode_withdraw_payload(&CrossChainWithdrawPayload {
+                    request_type: UnLockedWithdrawRequest,
+                    tokens: dst_tokens,
+                    amounts: dst_amounts,
+                    depositor: self.from,
+                    recipient: params.recipient,
+                    message: HexString::from_vec(vec![]),
+                })?;
+                Ok((payload, adapter.chain_type()))
+            });
+        if res.is_err() {
+            return Err(format!("withdraw payload: {:?}", res.err()).into());
+        }
+        let (payload, chain_type) = res.unwrap();
+        let src_nonce = crosschain.increase_nonce();
+        // withdraw waits in batch of dst chain, signed along with others once batch is sealed
+        if crosschain.get_batch_config(dst_chain_id.clone()).is_some() {
+            let mut request = CrossChainExecutionResult::new(self.hash.clone());
+            request.dst_chain_id = Some(dst_chain_id.clone());
+            request.payload = Some(payload);
+            request.fees = fees.clone();
+            request.update_status(CrossChainRequestStatus::Initiated, block_number);
+            crosschain.add_request(digichain.chain_id.clone(), src_nonce, request);
+            crosschain.add_to_batch(dst_chain_id, src_nonce, block_number);
+        } else {
+            let udata = digichain.get_cmp_ccr_data(
+                UnLockedWithdrawRequest,
+                digichain.chain_id.clone(),
+                dst_chain_id.clone(),
+                src_nonce,
+                Uint128::from(0u128),
+            );
+
+            let mut proposal = Proposal::new(
+                digichain.chain_id.clone(),
+                ProposalType::CrossChainRequest(udata.clone()),
+                self.from,
+                timestamp,
+                HexString::from_vec(vec![]),
+                self.nonce,
+                block_number,
+                Some(ExtraData::WithdrawData(CrossChainWithdrawMsg::new(
+                    dst_chain_id.clone(),
+                    digichain.chain_id.clone(),
+                    src_nonce,
+                    payload.clone(),
+                    digichain.get_validator_set_id(),
+                    chain_type,
+                ))),
+            );
+            let mut request = CrossChainExecutionResult::new(proposal.hash.clone());
+            request.dst_chain_id = Some(dst_chain_id);
+            request.payload = Some(payload);
+            request.fees = fees.clone();
+            request.update_status(CrossChainRequestStatus::Initiated, block_number);
+            crosschain.add_request(digichain.chain_id.clone(), src_nonce, request);
+            digichain.sign_proposal(&mut proposal);
+            let _ = mempool.add_proposal(ProposalType::CrossChainRequest(udata), proposal);
+        }
+        // src nonce and charged fees
+        Ok(encode(&vec![
+            Token::Uint(U256::from(src_nonce.u128())),
+            Token::Array(
+                fees.iter()
+                    .map(|fee| Token::String(fee.token_id.clone()))
+                    .collect(),
+            ),
+            Token::Array(
+                fees.iter()
+                    .map(|fee| Token::Uint(U256::from(fee.amount.u128())))
+                    .collect(),
+            ),
+        ]))
+    }
+
+    fn crosschain_send_message(
+        &mut self,
+        dst_chain_id: String,
+        block_number: u64,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        let params = get_crosschain_message_payload_params(self.data.clone());
+        if params.is_err() {
+            return Err(format!("decoding data: {:?}", params.err()).into());
+        }
+        let params: crate::types::TxCrossChainSendMessageParams = params.unwrap();
+        let mut crosschain = digichain.crosschain.write().unwrap();
+        if crosschain.is_chain_disabled(dst_chain_id.clone()) {
+            return Err(format!("chain {} is disabled", dst_chain_id).into());
+        }
+        // no funds, sender and contract travel in same payload as withdraw
+        let res = crosschain
+            .get_chain_adapter(dst_chain_id.clone())
+            .and_then(|adapter| {
+                let payload = adapter.encode_withdraw_payload(&CrossChainWithdrawPayload {
+                    request_type: MessageRequest,
+                    tokens: vec![],
+                    amounts: vec![],
+                    depositor: self.from,
+                    recipient: params.recipient,
+                    message: params.message.clone(),
+                })?;
+                Ok((payload, adapter.chain_type()))
+            });
+        if res.is_err() {
+            return Err(format!("message payload: {:?}", res.err()).into());
+        }
+        let (payload, chain_type) = res.unwrap();
         let src_nonce = crosschain.increase_nonce();
         let udata = digichain.get_cmp_ccr_data(
-            &mut crosschain,
-            UnLockedWithdrawRequest,
+            MessageRequest,
             digichain.chain_id.clone(),
             dst_chain_id.clone(),
             src_nonce,
             Uint128::from(0u128),
         );
-
-        let proposal = Proposal::new(
+        let mut proposal = Proposal::new(
             digichain.chain_id.clone(),
             ProposalType::CrossChainRequest(udata.clone()),
             self.from,
@@ -393,22 +740,26 @@ impl Transaction {
             HexString::from_vec(vec![]),
             self.nonce,
             block_number,
-            Some(ExtraData::WithdrawData(CrossChainWithdrawMsg {
-                dst_chain_id,
-                src_chain_id: digichain.chain_id.clone(),
+            Some(ExtraData::WithdrawData(CrossChainWithdrawMsg::new(
+                dst_chain_id.clone(),
+                digichain.chain_id.clone(),
                 src_nonce,
-                payload: get_crosschain_withdraw_payload(
-                    UnLockedWithdrawRequest,
-                    dst_tokens,
-                    dst_amounts,
-                    self.from,
-                    params.recipient,
-                    vec![],
-                ),
-                sigs: vec![],
-            })),
+                payload.clone(),
+                digichain.get_validator_set_id(),
+                chain_type,
+            ))),
         );
-        let _ = mempool.add_proposal(ProposalType::CrossChainRequest(udata), proposal);
+        let mut request = CrossChainExecutionResult::new(proposal.hash.clone());
+        request.dst_chain_id = Some(dst_chain_id);
+        request.payload = Some(payload);
+        request.update_status(CrossChainRequestStatus::Initiated, block_number);
+        crosschain.add_request(digichain.chain_id.clone(), src_nonce, request);
+        digichain.sign_proposal(&mut proposal);
+        let _ = digichain
+            .mempool
+            .write()
+            .unwrap()
+            .add_proposal(ProposalType::CrossChainRequest(udata), proposal);
         Ok(vec![])
     }
 
@@ -420,7 +771,7 @@ impl Transaction {
     ) -> Result<Vec<u8>, Box<dyn StdError>> {
         let mut mempool = digichain.mempool.write().unwrap();
         // is kyc data valid? for now yes
-        let proposal = Proposal::new(
+        let mut proposal = Proposal::new(
             digichain.chain_id.clone(),
             ProposalType::UserKYC,
             self.from,
@@ -430,6 +781,7 @@ impl Transaction {
             block_number,
             None,
         );
+        digichain.sign_proposal(&mut proposal);
         let _ = mempool.add_proposal(ProposalType::UserKYC, proposal);
         Ok(vec![])
     }
@@ -442,7 +794,6 @@ impl Transaction {
         digichain: &mut DigiChain,
     ) -> Result<Vec<u8>, Box<dyn StdError>> {
         let mut mempool = digichain.mempool.write().unwrap();
-        let mut crosschain = digichain.crosschain.write().unwrap();
 
         //TODO: is crosschain request data valid? for now yes
         let info = decode_crosschain_request_type_data(&data);
@@ -451,14 +802,13 @@ impl Transaction {
         }
         let info = info.unwrap();
         let udata = digichain.get_cmp_ccr_data(
-            &mut crosschain,
             info.request_type,
             info.src_chain_id,
             info.dst_chain_id.clone(),
             info.src_nonce,
             info.dst_nonce,
         );
-        let proposal = Proposal::new(
+        let mut proposal = Proposal::new(
             digichain.chain_id.clone(),
             ProposalType::CrossChainRequest(udata.clone()),
             self.from.clone(),
@@ -466,8 +816,9 @@ impl Transaction {
             self.data.clone(),
             self.nonce,
             block_number,
-            None,
+            self.proof.clone().map(ExtraData::ReceiptProof),
         );
+        digichain.sign_proposal(&mut proposal);
         let _ = mempool.add_proposal(ProposalType::CrossChainRequest(udata), proposal);
         Ok(vec![])
     }
@@ -480,7 +831,7 @@ impl Transaction {
     ) -> Result<Vec<u8>, Box<dyn StdError>> {
         let mut mempool = digichain.mempool.write().unwrap();
         //TODO: is add token data valid? for now yes
-        let proposal = Proposal::new(
+        let mut proposal = Proposal::new(
             digichain.chain_id.clone(),
             ProposalType::AddToken,
             self.from,
@@ -490,6 +841,7 @@ impl Transaction {
             block_number,
             None,
         );
+        digichain.sign_proposal(&mut proposal);
         let _ = mempool.add_proposal(ProposalType::AddToken, proposal);
         Ok(vec![])
     }
@@ -502,7 +854,7 @@ impl Transaction {
     ) -> Result<Vec<u8>, Box<dyn StdError>> {
         let mut mempool = digichain.mempool.write().unwrap();
         //TODO: is add_contract_config data valid? for now yes
-        let proposal = Proposal::new(
+        let mut proposal = Proposal::new(
             digichain.chain_id.clone(),
             ProposalType::AddContractConfig,
             self.from,
@@ -512,10 +864,38 @@ impl Transaction {
             block_number,
             None,
         );
+        digichain.sign_proposal(&mut proposal);
         let _ = mempool.add_proposal(ProposalType::AddContractConfig, proposal);
         Ok(vec![])
     }
 
+    // bridge config changes only by validator proposal
+    fn propose_governance_change(
+        &mut self,
+        proposal_type: ProposalType,
+        block_number: u64,
+        timestamp: u64,
+        digichain: &mut DigiChain,
+    ) -> Result<Vec<u8>, Box<dyn StdError>> {
+        if !digichain.is_validator(self.from) {
+            return Err(format!("only validator can propose {}", proposal_type.to_string()).into());
+        }
+        let mut mempool = digichain.mempool.write().unwrap();
+        let mut proposal = Proposal::new(
+            digichain.chain_id.clone(),
+            proposal_type.clone(),
+            self.from,
+            timestamp,
+            self.data.clone(),
+            self.nonce,
+            block_number,
+            None,
+        );
+        digichain.sign_proposal(&mut proposal);
+        let _ = mempool.add_proposal(proposal_type, proposal);
+        Ok(vec![])
+    }
+
     fn update_tokens_price(
         &mut self,
         block_number: u64,
@@ -524,7 +904,7 @@ impl Transaction {
     ) -> Result<Vec<u8>, Box<dyn StdError>> {
         let mut mempool = digichain.mempool.write().unwrap();
         //TODO: is add_contract_config data valid? for now yes
-        let proposal = Proposal::new(
+        let mut proposal = Proposal::new(
             digichain.chain_id.clone(),
             ProposalType::UpdateTokensPrice,
             self.from,
@@ -534,6 +914,7 @@ impl Transaction {
             block_number,
             None,
         );
+        digichain.sign_proposal(&mut proposal);
         let _ = mempool.add_proposal(ProposalType::UpdateTokensPrice, proposal);
         Ok(vec![])
     }
@@ -583,6 +964,67 @@ fn get_update_accepts_payload(
     Ok(TokenAcceptsParams { tokens, amounts })
 }
 
+// fee token and fee for transferring amount of token, flat + bps of value in fee token
+fn get_transfer_fee(
+    config: &FeeConfig,
+    token_list: &HashMap<TokenId, DigiToken>,
+    token_id: TokenId,
+    amount: Uint128,
+) -> Result<(TokenId, Uint128), Box<dyn StdError>> {
+    let fee_token_id = config.fee_token.clone().unwrap_or(token_id.clone());
+    if fee_token_id == token_id {
+        let res = config
+            .flat
+            .checked_add(amount.multiply_ratio(config.bps, 10000u128));
+        if res.is_err() {
+            return Err(format!("fee overflow: {:?}", res.err()).into());
+        }
+        return Ok((fee_token_id, res.unwrap()));
+    }
+    let res = token_list.get(&token_id);
+    if res.is_none() {
+        return Err(format!("token not found {:?}", token_id).into());
+    }
+    let token = res.unwrap();
+    let res = token_list.get(&fee_token_id);
+    if res.is_none() {
+        return Err(format!("fee token not found {:?}", fee_token_id).into());
+    }
+    let fee_token = res.unwrap();
+    if fee_token.price.is_zero() {
+        return Err(format!("fee token {:?} has no price", fee_token_id).into());
+    }
+    // value of amount in fee token, prices are per whole token
+    let res = amount.checked_multiply_ratio(token.price, fee_token.price);
+    if res.is_err() {
+        return Err(format!("fee value overflow: {:?}", res.err()).into());
+    }
+    let value = res.unwrap();
+    let res = 10u128.checked_pow(fee_token.decimal.abs_diff(token.decimal) as u32);
+    if res.is_none() {
+        return Err(format!(
+            "decimal difference too large: {} -> {}",
+            token.decimal, fee_token.decimal
+        )
+        .into());
+    }
+    let value = if fee_token.decimal >= token.decimal {
+        value.checked_mul(Uint128::from(res.unwrap()))
+    } else {
+        Ok(value.multiply_ratio(1u128, res.unwrap()))
+    };
+    if value.is_err() {
+        return Err(format!("fee value overflow: {:?}", value.err()).into());
+    }
+    let res = config
+        .flat
+        .checked_add(value.unwrap().multiply_ratio(config.bps, 10000u128));
+    if res.is_err() {
+        return Err(format!("fee overflow: {:?}", res.err()).into());
+    }
+    Ok((fee_token_id, res.unwrap()))
+}
+
 impl RawTransaction {
     pub fn to_byte(&self) -> Result<Vec<u8>, serde_json::Error> {
         serde_json::to_vec(self)
@@ -635,12 +1077,108 @@ fn parse_tx_type(value: &str) -> Option<TxType> {
         "CrossChainRequest" => Some(TxType::CrossChainRequest(HexString::from_str(
             additional_info,
         ))),
+        "CrossChainMessage" => Some(TxType::CrossChainMessage(additional_info.to_string())),
         "UserKYC" => Some(TxType::UserKYC),
         "None" => Some(TxType::None),
         "AddContractConfig" => Some(TxType::AddContractConfig),
+        "UpdateContractConfig" => Some(TxType::UpdateContractConfig),
+        "DisableChain" => Some(TxType::DisableChain),
+        "RemoveContractConfig" => Some(TxType::RemoveContractConfig),
+        "UpdateRateLimit" => Some(TxType::UpdateRateLimit),
+        "RegisterMessageHandler" => Some(TxType::RegisterMessageHandler),
+        "UpdateFeeConfig" => Some(TxType::UpdateFeeConfig),
+        "UpdateBatchConfig" => Some(TxType::UpdateBatchConfig),
+        "UpdateLightClient" => Some(TxType::UpdateLightClient),
         "AddToken" => Some(TxType::AddToken),
         "UpdateTokenAccepts" => Some(TxType::UpdateTokenAccepts),
         "UpdateTokensPrice" => Some(TxType::UpdateTokensPrice),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{signers::LocalWallet, types::U256, utils::keccak256};
+    use std::str::FromStr;
+
+    fn get_wallet() -> LocalWallet {
+        LocalWallet::from_str("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+            .unwrap()
+    }
+
+    fn get_tx(from: Address) -> Transaction {
+        Transaction {
+            hash: String::new(),
+            created_at: 1700000000u64,
+            chain_id: "digichain".to_string(),
+            timestamp: 0u64,
+            nonce: Uint128::from(5u128),
+            from,
+            data: HexString::from_vec(vec![4u8, 5u8]),
+            signature: Signature {
+                r: U256::zero(),
+                s: U256::zero(),
+                v: 0u64,
+            },
+            block_number: 0u64,
+            result: TxExecutionResult::None,
+            tx_type: TxType::CrossChainRequest(HexString::from_vec(vec![1u8, 2u8, 3u8])),
+            proof: None,
+        }
+    }
+
+    #[test]
+    fn signs_personal_message_of_json_payload() {
+        let wallet = get_wallet();
+        assert_eq!(
+            format!("{:?}", wallet.address()),
+            "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+        );
+        let mut tx = get_tx(wallet.address());
+        tx.sign(&wallet).unwrap();
+        let raw_tx = tx.get_raw_tx();
+        // keccak256("\x19Ethereum Signed Message:\n" || len || json payload)
+        let payload = raw_tx.get_signing_payload();
+        let mut message = format!("\x19Ethereum Signed Message:\n{}", payload.len()).into_bytes();
+        message.extend(payload);
+        assert_eq!(raw_tx.get_signing_digest(), H256::from(keccak256(message)));
+        assert!(raw_tx.verify_signature().is_ok());
+
+        let mut tampered = tx.get_raw_tx();
+        tampered.nonce = Uint128::from(6u128);
+        assert!(tampered.verify_signature().is_err());
+
+        // signed by other wallet for this sender
+        let other = LocalWallet::new(&mut rand::thread_rng());
+        tx.signature = other.sign_hash(raw_tx.get_signing_digest()).unwrap();
+        assert!(tx.get_raw_tx().verify_signature().is_err());
+        assert!(tx.sign(&other).is_err());
+    }
+
+    #[actix_web::test]
+    async fn verifies_typed_data_signature() {
+        let wallet = get_wallet();
+        let mut tx = get_tx(wallet.address());
+        let typed_data = get_tx_typed_data(&tx.get_raw_tx().get_signing_fields()).unwrap();
+        tx.signature = wallet.sign_typed_data(&typed_data).await.unwrap();
+        assert_eq!(
+            tx.signature
+                .recover(get_typed_data_digest(&typed_data).unwrap())
+                .unwrap(),
+            wallet.address()
+        );
+        assert!(tx.get_raw_tx().verify_signature().is_ok());
+
+        let mut tampered = tx.get_raw_tx();
+        tampered.data = HexString::from_vec(vec![4u8, 6u8]);
+        assert!(tampered.verify_signature().is_err());
+        tampered = tx.get_raw_tx();
+        tampered.chain_id = "other".to_string();
+        assert!(tampered.verify_signature().is_err());
+
+        let other = LocalWallet::new(&mut rand::thread_rng());
+        tx.signature = other.sign_typed_data(&typed_data).await.unwrap();
+        assert!(tx.get_raw_tx().verify_signature().is_err());
+    }
+}
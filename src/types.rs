@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     proposal::{ProposalType, RawProposal},
-    transaction::{RawTransaction, Transaction},
+    transaction::{RawTransaction, Transaction, TxSigningPayload},
 };
 use cosmwasm_std::Uint128;
 use ethers::{types::Signature, utils::hex::FromHexError};
@@ -144,6 +144,8 @@ pub struct AddTokenParams {
     pub decimal: u8,
     pub price: Uint128,                               // dollar value * 10^9
     pub chain_token_mapping: HashMap<String, String>, // chain_id : token_address on chain with chainid chain_id
+    #[serde(default)]
+    pub chain_decimals: HashMap<String, u8>, // chain_id : token decimal on chain with chainid chain_id
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -151,7 +153,63 @@ pub struct AddContractConfigParams {
     pub contract_address: String,
     pub start_block: u64,
     pub chain_id: String,
-    pub chain_type: u8,
+    pub chain_type: u8, // ChainTypeEvm or ChainTypeCosmWasm
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DisableChainParams {
+    pub chain_id: String,
+    pub disabled: bool, // false enables it back
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoveContractConfigParams {
+    pub chain_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RateLimitConfig {
+    pub window: u64,          // rolling window in blocks
+    pub cap: Uint128,         // max amount of token per chain in window, 0 means no cap
+    pub account_cap: Uint128, // max amount of token per account in window, 0 means no cap
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateRateLimitParams {
+    pub chain_id: String,
+    pub token_id: TokenId,
+    pub config: RateLimitConfig, // window 0 removes limit
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegisterMessageHandlerParams {
+    pub handler: Address,
+    pub kind: String, // native handler kind, empty removes handler
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FeeConfig {
+    pub flat: Uint128,              // flat fee per transferred token, in fee token
+    pub bps: u64,                   // basis points of transferred value
+    pub fee_token: Option<TokenId>, // token fee is paid in, bridged token if none
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateFeeConfigParams {
+    pub chain_id: String,
+    pub config: FeeConfig, // zero flat and bps removes fee
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchConfig {
+    pub max_blocks: u64, // batch is sealed once open for these many blocks
+    pub max_size: u64,   // or once it holds these many withdraws
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateBatchConfigParams {
+    pub chain_id: String,
+    pub config: BatchConfig, // zero max_size removes batching
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -166,7 +224,9 @@ pub struct ContractConfig {
     pub start_block: u64,
     pub last_processed_block: u64,
     pub last_proccessed_nonce: Uint128,
-    pub chain_type: u8,
+    pub chain_type: u8, // ChainTypeEvm or ChainTypeCosmWasm
+    #[serde(default)]
+    pub disabled: bool, // paused chain, no inbound or outbound request
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -252,6 +312,46 @@ pub struct IsBroadcastedParams {
     pub src_nonce: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetCrossChainRequestStatusParams {
+    pub src_chain_id: String,
+    pub src_nonce: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetInboundNonceGapsParams {
+    pub src_chain_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateLightClientParams {
+    pub chain_id: String,
+    pub headers: Vec<Vec<u8>>, // rlp encoded headers
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetWithdrawBatchProofParams {
+    pub src_nonce: Uint128, // nonce of batched withdraw
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetLightClientHeadParams {
+    pub chain_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetTypedDataParams {
+    pub transaction: TxSigningPayload, // unsigned tx
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GetBridgeReservesParams {
+    #[serde(default)]
+    pub token_ids: Vec<TokenId>, // all tokens if empty
+    #[serde(default)]
+    pub chain_id: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct GetBalances {
     pub tokens: Vec<String>,
@@ -294,6 +394,16 @@ pub struct GetOptimalPath {
     pub amount: Uint128, // dollar multiplied by 10^9
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrossChainWithdrawPayload {
+    pub request_type: u8,
+    pub tokens: Vec<Address>,
+    pub amounts: Vec<Uint128>,
+    pub depositor: Address,
+    pub recipient: Address,
+    pub message: HexString,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct CrossChainRequestTypeData {
     pub request_type: u8,
@@ -304,6 +414,25 @@ pub struct CrossChainRequestTypeData {
     pub validator: Address,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TxCrossChainMessageParams {
+    pub src_chain_id: String,
+    pub dst_chain_id: String,
+    pub src_contract: Address,
+    pub sender: Address,  // sender on src chain
+    pub handler: Address, // handler registered here
+    pub message: HexString,
+    pub src_nonce: Uint128,
+    pub src_block_number: u64,
+    pub src_tx_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TxCrossChainSendMessageParams {
+    pub recipient: Address, // contract on dst chain
+    pub message: HexString,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct TxCrossChainReplyParams {
     pub src_chain_id: String,
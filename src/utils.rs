@@ -1,10 +1,12 @@
 use crate::{
     block::DigiBlock,
+    constants::BatchWithdrawRequest,
     digichain::DigiChain,
     token::DigiToken,
     types::{
-        CrossChainRequestTypeData, HexString, TokenId, TxCrossChainTransferParams,
-        TxExecutionResult, TxTransferParams,
+        CrossChainRequestTypeData, CrossChainWithdrawPayload, HexString, TokenId,
+        TxCrossChainSendMessageParams, TxCrossChainTransferParams, TxExecutionResult,
+        TxTransferParams,
     },
     validators::Validator,
 };
@@ -12,7 +14,10 @@ use cosmwasm_std::Uint128;
 use ethers::signers::{LocalWallet, Wallet};
 use ethers::types::Signature;
 use ethers::{contract, core::types::transaction::eip712::Eip712};
-use ethers::{prelude::*, utils::hash_message};
+use ethers::{
+    prelude::*,
+    utils::{hash_message, keccak256},
+};
 use router_wasm_bindings::ethabi::{
     decode, encode, ethereum_types::U256, Address as EthRouterAddress, ParamType, Token,
 };
@@ -34,6 +39,31 @@ pub fn is_within_slippage(
     resulted_tokens_price >= lower_limit && resulted_tokens_price <= upper_limit
 }
 
+// uint of abi decoded untrusted data, out of range value is an error instead of panic
+pub fn uint_to_u128(value: U256, name: &str) -> Result<Uint128, Box<dyn StdError>> {
+    let res = u128::try_from(value);
+    if res.is_err() {
+        return Err(format!("{} out of range", name).into());
+    }
+    Ok(Uint128::from(res.unwrap()))
+}
+
+pub fn uint_to_u64(value: U256, name: &str) -> Result<u64, Box<dyn StdError>> {
+    let res = u64::try_from(value);
+    if res.is_err() {
+        return Err(format!("{} out of range", name).into());
+    }
+    Ok(res.unwrap())
+}
+
+pub fn uint_to_u8(value: U256, name: &str) -> Result<u8, Box<dyn StdError>> {
+    let res = u8::try_from(value);
+    if res.is_err() {
+        return Err(format!("{} out of range", name).into());
+    }
+    Ok(res.unwrap())
+}
+
 pub fn address_to_str(address: Address) -> String {
     format!("0x{:x}", address)
 }
@@ -201,6 +231,45 @@ pub fn get_crosschain_transfer_payload_params(
     })
 }
 
+pub fn get_crosschain_message_payload_params(
+    hex_data: HexString,
+) -> Result<TxCrossChainSendMessageParams, Box<dyn StdError>> {
+    let data = hex_data.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::Address, //recipient contract
+            ParamType::Bytes,   //message
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    // recipient
+    let res = decoded_data[0].clone().into_address();
+    if res.is_none() {
+        return Err("recipient is none".into());
+    }
+    let recipient = res.unwrap();
+
+    // message
+    let res = decoded_data[1].clone().into_bytes();
+    if res.is_none() {
+        return Err("message is none".into());
+    }
+    let message = res.unwrap();
+
+    Ok(TxCrossChainSendMessageParams {
+        recipient: Address::from_slice(recipient.as_bytes()),
+        message: HexString::from_vec(message),
+    })
+}
+
 pub fn decode_crosschain_request_type_data(
     data: &HexString,
 ) -> Result<CrossChainRequestTypeData, Box<dyn std::error::Error>> {
@@ -229,7 +298,7 @@ pub fn decode_crosschain_request_type_data(
     if res.is_none() {
         return Err("request_type is none".into());
     }
-    let request_type = res.unwrap().as_u32() as u8;
+    let request_type = uint_to_u8(res.unwrap(), "request_type")?;
 
     //src_chain_id
     let res = decoded_data[1].clone().into_string();
@@ -243,7 +312,7 @@ pub fn decode_crosschain_request_type_data(
     if res.is_none() {
         return Err("src_nonce is none".into());
     }
-    let src_nonce = Uint128::from(res.unwrap().as_u128());
+    let src_nonce = uint_to_u128(res.unwrap(), "src_nonce")?;
 
     //dst_chain_id
     let res = decoded_data[3].clone().into_string();
@@ -257,7 +326,7 @@ pub fn decode_crosschain_request_type_data(
     if res.is_none() {
         return Err("dst_nonce is none".into());
     }
-    let dst_nonce = Uint128::from(res.unwrap().as_u128());
+    let dst_nonce = uint_to_u128(res.unwrap(), "dst_nonce")?;
 
     //validator
     let res = decoded_data[5].clone().into_address();
@@ -335,6 +404,211 @@ pub fn get_crosschain_withdraw_payload(
     HexString::from_vec(edata)
 }
 
+// abi.encode(request_type, root, src_nonces) of batched withdraws
+pub fn get_crosschain_batch_payload(root: H256, src_nonces: &Vec<Uint128>) -> HexString {
+    let edata = encode(&vec![
+        Token::Uint(U256::from(BatchWithdrawRequest)),
+        Token::FixedBytes(root.as_bytes().to_vec()),
+        Token::Array(
+            src_nonces
+                .iter()
+                .map(|src_nonce| Token::Uint(U256::from(src_nonce.u128())))
+                .collect(),
+        ),
+    ]);
+    HexString::from_vec(edata)
+}
+
+pub fn decode_crosschain_withdraw_payload(
+    payload: &HexString,
+) -> Result<CrossChainWithdrawPayload, Box<dyn StdError>> {
+    let data = payload.to_vec();
+    if data.is_err() {
+        return Err(format!("{:?}", data.err()).into());
+    }
+    let decoded_data = decode(
+        &[
+            ParamType::Uint(8),                               //request type
+            ParamType::Array(Box::new(ParamType::Address)),   //tokens
+            ParamType::Array(Box::new(ParamType::Uint(256))), //amounts
+            ParamType::Address,                               //depositor
+            ParamType::Address,                               //recipient
+            ParamType::Bytes,                                 //message
+        ],
+        &data.unwrap(),
+    );
+    if decoded_data.is_err() {
+        return Err(format!("{:?}", decoded_data.err()).into());
+    }
+    let decoded_data = decoded_data.unwrap();
+
+    //request_type
+    let res = decoded_data[0].clone().into_uint();
+    if res.is_none() {
+        return Err("request_type is none".into());
+    }
+    let request_type = uint_to_u8(res.unwrap(), "request_type")?;
+
+    // tokens
+    let res = decoded_data[1].clone().into_array();
+    if res.is_none() {
+        return Err("tokens is none".into());
+    }
+    let tokens = res
+        .unwrap()
+        .into_iter()
+        .filter_map(|t| {
+            let res = t.into_address();
+            if res.is_none() {
+                return None;
+            }
+            Some(Address::from_slice(res.unwrap().as_bytes()))
+        })
+        .collect::<Vec<Address>>();
+
+    // amounts
+    let res = decoded_data[2].clone().into_array();
+    if res.is_none() {
+        return Err("amounts is none".into());
+    }
+    let mut amounts: Vec<Uint128> = Vec::new();
+    for t in res.unwrap() {
+        let res = t.into_uint();
+        if res.is_none() {
+            continue;
+        }
+        amounts.push(uint_to_u128(res.unwrap(), "amount")?);
+    }
+
+    //depositor
+    let res = decoded_data[3].clone().into_address();
+    if res.is_none() {
+        return Err("depositor is none".into());
+    }
+    let depositor = Address::from_slice(res.unwrap().as_bytes());
+
+    //recipient
+    let res = decoded_data[4].clone().into_address();
+    if res.is_none() {
+        return Err("recipient is none".into());
+    }
+    let recipient = Address::from_slice(res.unwrap().as_bytes());
+
+    //message
+    let res = decoded_data[5].clone().into_bytes();
+    if res.is_none() {
+        return Err("message is none".into());
+    }
+    let message = HexString::from_vec(res.unwrap());
+
+    Ok(CrossChainWithdrawPayload {
+        request_type,
+        tokens,
+        amounts,
+        depositor,
+        recipient,
+        message,
+    })
+}
+
+// keccak256(abi.encode(src_chain_id, dst_chain_id, src_nonce, payload)), msg signed by validators for withdraw
+pub fn get_crosschain_withdraw_msg_hash(
+    src_chain_id: String,
+    dst_chain_id: String,
+    src_nonce: Uint128,
+    payload: &HexString,
+) -> Result<[u8; 32], Box<dyn StdError>> {
+    let payload = payload.to_vec();
+    if payload.is_err() {
+        return Err(format!("{:?}", payload.err()).into());
+    }
+    let edata = encode(&vec![
+        Token::String(src_chain_id),
+        Token::String(dst_chain_id),
+        Token::Uint(U256::from(src_nonce.u128())),
+        Token::Bytes(payload.unwrap()),
+    ]);
+    Ok(keccak256(edata))
+}
+
+// withdraw msg hash with eth signed message prefix, so that gateway on dst chain can verify it using ecrecover
+pub fn get_crosschain_withdraw_digest(
+    src_chain_id: String,
+    dst_chain_id: String,
+    src_nonce: Uint128,
+    payload: &HexString,
+) -> Result<H256, Box<dyn StdError>> {
+    let msg_hash =
+        get_crosschain_withdraw_msg_hash(src_chain_id, dst_chain_id, src_nonce, payload)?;
+    Ok(hash_message(msg_hash))
+}
+
+// hash of sorted pair, so proof needs no left/right flags, odd node is carried to next level
+pub fn get_merkle_root(leaves: &Vec<[u8; 32]>) -> [u8; 32] {
+    if leaves.len() == 0usize {
+        return [0u8; 32];
+    }
+    let mut level = leaves.clone();
+    while level.len() > 1usize {
+        level = level
+            .chunks(2usize)
+            .map(|pair| {
+                if pair.len() == 1usize {
+                    return pair[0];
+                }
+                hash_merkle_pair(pair[0], pair[1])
+            })
+            .collect();
+    }
+    level[0]
+}
+
+// siblings from leaf at idx up to root
+pub fn get_merkle_proof(
+    leaves: &Vec<[u8; 32]>,
+    idx: usize,
+) -> Result<Vec<[u8; 32]>, Box<dyn StdError>> {
+    if idx >= leaves.len() {
+        return Err(format!("leaf {} not in tree of {} leaves", idx, leaves.len()).into());
+    }
+    let mut proof: Vec<[u8; 32]> = Vec::new();
+    let mut level = leaves.clone();
+    let mut idx = idx;
+    while level.len() > 1usize {
+        let sibling = idx ^ 1usize;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        level = level
+            .chunks(2usize)
+            .map(|pair| {
+                if pair.len() == 1usize {
+                    return pair[0];
+                }
+                hash_merkle_pair(pair[0], pair[1])
+            })
+            .collect();
+        idx = idx / 2usize;
+    }
+    Ok(proof)
+}
+
+pub fn verify_merkle_proof(root: [u8; 32], leaf: [u8; 32], proof: &Vec<[u8; 32]>) -> bool {
+    let mut hash = leaf;
+    for sibling in proof {
+        hash = hash_merkle_pair(hash, *sibling);
+    }
+    hash == root
+}
+
+fn hash_merkle_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak256([a, b].concat())
+    } else {
+        keccak256([b, a].concat())
+    }
+}
+
 pub fn abs(a: Uint128, b: Uint128) -> Uint128 {
     if a > b {
         a - b
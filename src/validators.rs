@@ -1,4 +1,5 @@
 use crate::acccount::Account;
+use crate::types::HexString;
 use cosmwasm_std::Uint128;
 use ethers::core::k256::ecdsa::SigningKey;
 use ethers_signers::Wallet;
@@ -9,6 +10,10 @@ pub struct Validator {
     pub acccount: Account,
     pub staked: Uint128,
     pub wallet: Wallet<SigningKey>,
+    pub bls_public_key: Option<HexString>, // registered along with wallet, used to verify aggregated withdraw sig
+    pub bls_pop: Option<HexString>, // proof of possession of bls key, required before key is aggregated
+    #[cfg(feature = "bls")]
+    pub bls_secret_key: Option<blst::min_pk::SecretKey>,
 }
 
 impl Validator {
@@ -17,8 +22,20 @@ impl Validator {
             acccount,
             staked,
             wallet,
+            bls_public_key: None,
+            bls_pop: None,
+            #[cfg(feature = "bls")]
+            bls_secret_key: None,
         }
     }
+
+    // bls key is separate secret of validator, public key and its proof of possession are registered with it
+    #[cfg(feature = "bls")]
+    pub fn set_bls_secret_key(&mut self, secret_key: blst::min_pk::SecretKey) {
+        self.bls_public_key = Some(crate::bls::get_public_key(&secret_key));
+        self.bls_pop = Some(crate::bls::prove_possession(&secret_key));
+        self.bls_secret_key = Some(secret_key);
+    }
 }
 
 impl Default for Validator {
@@ -27,6 +44,10 @@ impl Default for Validator {
             acccount: Default::default(),
             staked: Default::default(),
             wallet: Wallet::new(&mut thread_rng()),
+            bls_public_key: None,
+            bls_pop: None,
+            #[cfg(feature = "bls")]
+            bls_secret_key: None,
         }
     }
 }